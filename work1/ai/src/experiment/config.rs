@@ -0,0 +1,429 @@
+//! The subset of TOML read by experiment files: `key = value` pairs under
+//! `[table]` and `[table.subtable]` headers, with integer, float, boolean,
+//! basic string and array values, and `#` comments. Arrays may span several
+//! lines.
+
+use std::fmt;
+
+use crate::parse::Error;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Array(Vec<Value>),
+    Table(Table),
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "an integer",
+            Self::Float(_) => "a float",
+            Self::Boolean(_) => "a boolean",
+            Self::String(_) => "a string",
+            Self::Array(_) => "an array",
+            Self::Table(_) => "a table",
+        }
+    }
+}
+
+/// Written back in the syntax it is parsed from, except for tables.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Float(x) if x.is_nan() => write!(f, "nan"),
+            Self::Float(x) if x.is_infinite() => {
+                write!(f, "{}inf", if *x < 0.0 { "-" } else { "" })
+            }
+            Self::Float(x) => write!(f, "{x:?}"),
+            Self::Boolean(b) => write!(f, "{b}"),
+            Self::String(s) => write!(f, "\"{}\"", escape(s)),
+            Self::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    let separator = if i == 0 { "" } else { ", " };
+                    write!(f, "{separator}{value}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Table(_) => write!(f, "{{...}}"),
+        }
+    }
+}
+
+/// A key and its value, with the line it was defined on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+}
+
+/// Entries in the order they were defined.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    pub entries: Vec<Entry>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    fn table_mut(&mut self, key: &str, line: usize) -> Result<&mut Table, Error> {
+        let i = match self.entries.iter().position(|entry| entry.key == key) {
+            Some(i) => i,
+            None => {
+                self.entries.push(Entry {
+                    key: key.to_string(),
+                    value: Value::Table(Table::default()),
+                    line,
+                });
+                self.entries.len() - 1
+            }
+        };
+
+        match &mut self.entries[i].value {
+            Value::Table(table) => Ok(table),
+            _ => Err(Error::at(
+                line,
+                format!("{key} is already defined as a value"),
+            )),
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Table, Error> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+        line: 1,
+    };
+
+    let mut root = Table::default();
+    let mut path = Vec::new();
+    let mut headers = Vec::<Vec<String>>::new();
+
+    loop {
+        parser.skip_blanks();
+        match parser.peek() {
+            None => break,
+            Some('\n') => {
+                parser.bump();
+                continue;
+            }
+            Some('#') => {
+                parser.skip_comment();
+                continue;
+            }
+            Some('[') => {
+                parser.bump();
+                if parser.peek() == Some('[') {
+                    return Err(parser.error("arrays of tables are not supported"));
+                }
+
+                path = vec![parser.key()?];
+                loop {
+                    parser.skip_blanks();
+                    match parser.bump() {
+                        Some('.') => path.push(parser.key()?),
+                        Some(']') => break,
+                        _ => return Err(parser.error("expected `.` or `]` in the table header")),
+                    }
+                }
+
+                if headers.contains(&path) {
+                    return Err(parser.error(format!("table {} is defined twice", path.join("."))));
+                }
+                headers.push(path.clone());
+
+                let mut table = &mut root;
+                for key in &path {
+                    table = table.table_mut(key, parser.line)?;
+                }
+            }
+            Some(_) => {
+                let line = parser.line;
+                let key = parser.key()?;
+
+                parser.skip_blanks();
+                if parser.bump() != Some('=') {
+                    return Err(parser.error(format!("expected `=` after {key}")));
+                }
+                parser.skip_blanks();
+                let value = parser.value()?;
+
+                let mut table = &mut root;
+                for key in &path {
+                    table = table.table_mut(key, line)?;
+                }
+
+                if table.get(&key).is_some() {
+                    return Err(Error::at(line, format!("{key} is defined twice")));
+                }
+                table.entries.push(Entry { key, value, line });
+            }
+        }
+
+        parser.skip_blanks();
+        match parser.peek() {
+            None | Some('\n') => {}
+            Some('#') => parser.skip_comment(),
+            Some(c) => return Err(parser.error(format!("unexpected `{c}` at the end of the line"))),
+        }
+    }
+
+    Ok(root)
+}
+
+/// Escapes `text` to be written inside a basic string.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::at(self.line, message)
+    }
+
+    // spaces and tabs, and carriage returns before a newline
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\r')) {
+            self.bump();
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while !matches!(self.peek(), None | Some('\n')) {
+            self.bump();
+        }
+    }
+
+    // blanks, newlines and comments, as allowed inside arrays
+    fn skip_space(&mut self) {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                Some('\n') => {
+                    self.bump();
+                }
+                Some('#') => self.skip_comment(),
+                _ => break,
+            }
+        }
+    }
+
+    fn key(&mut self) -> Result<String, Error> {
+        self.skip_blanks();
+        if self.peek() == Some('"') {
+            return self.string();
+        }
+
+        let mut key = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                key.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        if key.is_empty() {
+            Err(self.error("expected a key"))
+        } else {
+            Ok(key)
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        match self.peek() {
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('[') => self.array(),
+            _ => self.scalar(),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.bump();
+
+        let mut string = String::new();
+        loop {
+            let Some(c) = self.peek().filter(|&c| c != '\n') else {
+                return Err(self.error("unterminated string"));
+            };
+            self.bump();
+
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let c = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    string.push(c);
+                }
+                c => string.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, Error> {
+        self.bump();
+
+        let mut values = Vec::new();
+        loop {
+            self.skip_space();
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(Value::Array(values));
+            }
+
+            values.push(self.value()?);
+
+            self.skip_space();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(self.error("expected `,` or `]` in the array")),
+            }
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value, Error> {
+        let mut token = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.' | '_') {
+                token.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        match token.as_str() {
+            "" => return Err(self.error("expected a value")),
+            "true" => return Ok(Value::Boolean(true)),
+            "false" => return Ok(Value::Boolean(false)),
+            "inf" | "+inf" => return Ok(Value::Float(f64::INFINITY)),
+            "-inf" => return Ok(Value::Float(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => return Ok(Value::Float(f64::NAN)),
+            _ => {}
+        }
+
+        let digits = token.replace('_', "");
+        let radix = [("0x", 16), ("0o", 8), ("0b", 2)]
+            .into_iter()
+            .find(|(prefix, _)| digits.starts_with(prefix));
+
+        let value = if let Some((prefix, radix)) = radix {
+            i64::from_str_radix(&digits[prefix.len()..], radix)
+                .map(Value::Integer)
+                .ok()
+        } else if digits.contains(['.', 'e', 'E']) {
+            digits.parse().map(Value::Float).ok()
+        } else {
+            digits.parse().map(Value::Integer).ok()
+        };
+
+        value.ok_or_else(|| self.error(format!("invalid value `{token}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        let text = r#"
+# comment
+name = "a \"quoted\" name" # trailing comment
+runs = 1_000
+
+[budget]
+evaluations = 0x10
+target = -1e-8
+
+[algorithms.ga]
+rates = [
+    0.2, 0.15, # comment
+    inf,
+]
+"#;
+        let table = parse(text).unwrap();
+
+        let name = table.get("name").unwrap();
+        assert_eq!(name.value, Value::String("a \"quoted\" name".to_string()));
+        assert_eq!(name.line, 3);
+        assert_eq!(table.get("runs").unwrap().value, Value::Integer(1000));
+
+        let Value::Table(budget) = &table.get("budget").unwrap().value else {
+            panic!("budget is not a table");
+        };
+        assert_eq!(budget.get("evaluations").unwrap().value, Value::Integer(16));
+        assert_eq!(budget.get("target").unwrap().value, Value::Float(-1e-8));
+
+        let Value::Table(algorithms) = &table.get("algorithms").unwrap().value else {
+            panic!("algorithms is not a table");
+        };
+        let Value::Table(ga) = &algorithms.get("ga").unwrap().value else {
+            panic!("ga is not a table");
+        };
+        let rates = &ga.get("rates").unwrap().value;
+        assert_eq!(rates.to_string(), "[0.2, 0.15, inf]");
+    }
+
+    #[test]
+    fn errors_report_the_line() {
+        let cases = [
+            ("a = 1\na = 2", 2),
+            ("[t]\nx = 1\n[t]", 3),
+            ("a = [1,\n2\n3]", 3),
+            ("a = \"open", 1),
+            ("a = 1 b", 1),
+            ("\n\na = 1.2.3", 3),
+            ("a = 1\n[a]", 2),
+        ];
+
+        crate::parse::assert_error_lines(cases, parse);
+    }
+}
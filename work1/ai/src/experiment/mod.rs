@@ -0,0 +1,751 @@
+//! Experiments described by a configuration file, run over every combination
+//! of their problems and algorithm parameters.
+//!
+//! ```toml
+//! name = "engineering"
+//! output = "results/engineering"
+//! runs = 30
+//! seed = "xoshiro256+:93920339ac7730ac-8db68f4acc7c22b1-8b804df6a99a1289-ff5fa2f037375aa9"
+//! problems = ["TubularColumn", "FMSound"]
+//! baseline = "ga"     # optional, the first algorithm by default
+//! alpha = 0.05        # optional
+//! statistic = "mean"  # optional, "median" by default
+//!
+//! [budget]
+//! iterations = 1000   # and/or evaluations, and optionally a target
+//!
+//! [budget.FMSound]
+//! iterations = 10000
+//!
+//! [algorithms.ga]
+//! algorithm = "genetic_algorithm"
+//! population_size = 20
+//! mutation_probability = [0.2, 0.15, 0.1]
+//!
+//! [algorithms.ga.FMSound]
+//! population_size = 60
+//! ```
+//!
+//! Every table under `algorithms` gives the `algorithm` to run and its
+//! parameters, whose arrays are the values of a grid. The configurations of
+//! an algorithm are the Cartesian product of its grid, and are run on every
+//! problem. A subtable named after a problem overrides the budget or the
+//! parameters that are not part of the grid for that problem alone.
+//!
+//! Which problems and algorithms exist is up to the caller of
+//! [`Experiment::run`], which solves a [`Configuration`] at a time.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use rand::rngs::Xoshiro256Plus;
+
+use crate::metaheuristics::Termination;
+use crate::parse;
+use crate::plot::{self, Series};
+use crate::report::{Report, Statistic};
+use crate::stats::Summary;
+
+pub mod config;
+pub mod tuning;
+
+pub use config::{Entry, Table, Value};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The file could not be read, or one of its lines is not valid.
+    Read(parse::Error),
+    /// The experiment is not valid as a whole, such as when a key is missing
+    /// or names an unknown problem or algorithm.
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "{error}"),
+            Self::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<parse::Error> for Error {
+    fn from(error: parse::Error) -> Self {
+        Self::Read(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Read(error.into())
+    }
+}
+
+/// One point of the grid of an algorithm, on one problem.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    pub problem: String,
+    /// Name of the table of the algorithm, such as `ga`.
+    pub name: String,
+    /// The `algorithm` of the table, such as `genetic_algorithm`.
+    pub algorithm: String,
+    pub parameters: Vec<(String, Value)>,
+    pub termination: Termination,
+    // parameters given as arrays, which tell the configurations apart
+    pub(crate) grid: Vec<String>,
+}
+
+impl Configuration {
+    /// The name of the algorithm followed by its grid parameters, the same
+    /// on every problem, such as `ga(mutation_probability=0.2)`.
+    pub fn label(&self) -> String {
+        if self.grid.is_empty() {
+            return self.name.clone();
+        }
+
+        let grid = self
+            .grid
+            .iter()
+            .map(|key| format!("{key}={}", self.value(key).unwrap()))
+            .collect::<Vec<_>>();
+
+        format!("{}({})", self.name, grid.join(", "))
+    }
+
+    pub fn value(&self, key: &str) -> Result<&Value, Error> {
+        self.parameters
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                Error::Invalid(format!(
+                    "{} on {} is missing {key}",
+                    self.name, self.problem
+                ))
+            })
+    }
+
+    /// A float parameter, which may also be written as an integer.
+    pub fn f64(&self, key: &str) -> Result<f64, Error> {
+        match self.value(key)? {
+            &Value::Float(x) => Ok(x),
+            &Value::Integer(n) => Ok(n as f64),
+            value => Err(self.mismatch(key, "a number", value)),
+        }
+    }
+
+    pub fn usize(&self, key: &str) -> Result<usize, Error> {
+        match self.value(key)? {
+            &Value::Integer(n) if n >= 0 => Ok(n as usize),
+            value => Err(self.mismatch(key, "a non negative integer", value)),
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Result<bool, Error> {
+        match self.value(key)? {
+            &Value::Boolean(b) => Ok(b),
+            value => Err(self.mismatch(key, "a boolean", value)),
+        }
+    }
+
+    pub fn str(&self, key: &str) -> Result<&str, Error> {
+        match self.value(key)? {
+            Value::String(s) => Ok(s),
+            value => Err(self.mismatch(key, "a string", value)),
+        }
+    }
+
+    fn mismatch(&self, key: &str, expected: &str, value: &Value) -> Error {
+        Error::Invalid(format!(
+            "{key} of {} must be {expected}, found {value}",
+            self.name
+        ))
+    }
+}
+
+/// What a run of a configuration found.
+pub struct Run {
+    /// Objective value of the best point.
+    pub fitness: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+    /// Convergence history, as recorded by
+    /// [`History`](crate::metaheuristics::observer::History), or empty.
+    pub history: Vec<(usize, f64)>,
+}
+
+pub struct Experiment {
+    pub name: String,
+    pub output: PathBuf,
+    pub runs: usize,
+    /// Generator of the first run, jumped once more for every next one.
+    pub seed: Xoshiro256Plus,
+    pub problems: Vec<String>,
+    /// Every configuration of every algorithm on every problem, by problem.
+    pub configurations: Vec<Configuration>,
+    /// Index of the first configuration of the baseline algorithm among
+    /// those of a problem.
+    pub baseline: usize,
+    pub alpha: f64,
+    pub statistic: Statistic,
+    /// The text of the configuration file, recorded in the manifest.
+    pub text: String,
+}
+
+impl Experiment {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let root = config::parse(text)?;
+
+        check_keys(
+            &root,
+            &[
+                "name",
+                "output",
+                "runs",
+                "seed",
+                "problems",
+                "baseline",
+                "alpha",
+                "statistic",
+                "budget",
+                "algorithms",
+            ],
+        )?;
+
+        let name = string(required(&root, "name")?)?.to_string();
+        let output = PathBuf::from(string(required(&root, "output")?)?);
+        let runs = required(&root, "runs")?;
+        let runs = match integer(runs)? {
+            0 => return Err(invalid(runs, "there must be at least one run")),
+            n => n,
+        };
+
+        let seed = required(&root, "seed")?;
+        let seed = string(seed)?
+            .parse::<Xoshiro256Plus>()
+            .map_err(|error| invalid(seed, format!("invalid seed: {error}")))?;
+
+        let problems = required(&root, "problems")?;
+        let problems = match &problems.value {
+            Value::Array(values) if !values.is_empty() => values
+                .iter()
+                .map(|value| match value {
+                    Value::String(s) => Ok(s.clone()),
+                    _ => Err(invalid(problems, "problems must be strings")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => return Err(invalid(problems, "problems must be a non empty array")),
+        };
+
+        let alpha = match root.get("alpha") {
+            Some(entry) => match entry.value {
+                Value::Float(x) if 0.0 < x && x < 1.0 => x,
+                _ => return Err(invalid(entry, "alpha must be a float between 0 and 1")),
+            },
+            None => 0.05,
+        };
+
+        let statistic = match root.get("statistic") {
+            Some(entry) => match string(entry)? {
+                "mean" => Statistic::Mean,
+                "median" => Statistic::Median,
+                _ => return Err(invalid(entry, "statistic must be \"mean\" or \"median\"")),
+            },
+            None => Statistic::Median,
+        };
+
+        let budget = table(required(&root, "budget")?)?;
+        check_problems(budget, &problems)?;
+
+        let algorithms = required(&root, "algorithms")?;
+        let algorithms = table(algorithms)?;
+        if algorithms.entries.is_empty() {
+            return Err(Error::Invalid("there are no algorithms".to_string()));
+        }
+
+        let baseline_name = match root.get("baseline") {
+            Some(entry) => {
+                let baseline = string(entry)?;
+                if algorithms.get(baseline).is_none() {
+                    return Err(invalid(entry, format!("unknown baseline {baseline}")));
+                }
+                baseline.to_string()
+            }
+            None => algorithms.entries[0].key.clone(),
+        };
+
+        for algorithm in &algorithms.entries {
+            check_problems(table(algorithm)?, &problems)?;
+        }
+
+        let mut configurations = Vec::new();
+        let mut baseline = 0;
+        for (p, problem) in problems.iter().enumerate() {
+            let termination = termination(budget, problem)?;
+
+            for algorithm in &algorithms.entries {
+                if p == 0 && algorithm.key == baseline_name {
+                    baseline = configurations.len();
+                }
+                configurations.extend(expand(algorithm, problem, termination)?);
+            }
+        }
+
+        Ok(Self {
+            name,
+            output,
+            runs,
+            seed,
+            problems,
+            configurations,
+            baseline,
+            alpha,
+            statistic,
+            text: text.to_string(),
+        })
+    }
+
+    /// Generators of every run, which are the same for every configuration
+    /// so that they are compared on common random numbers.
+    pub fn seeds(&self) -> Vec<Xoshiro256Plus> {
+        let mut rng = self.seed.clone();
+
+        (0..self.runs)
+            .map(|_| {
+                let seed = rng.clone();
+                rng.jump();
+                seed
+            })
+            .collect()
+    }
+
+    /// Runs every configuration `runs` times with `solve`, writing to the
+    /// output directory:
+    ///
+    /// - `manifest.toml`, the text of the experiment and the seed of every run;
+    /// - `results.csv`, what every run found;
+    /// - `<problem>.svg`, the convergence of every configuration on a problem;
+    /// - `report.md` and `report.tex`, the results compared with the baseline.
+    ///
+    /// `solve` errors stop the experiment, keeping the results so far, and
+    /// `progress` is given the summary of every configuration once its runs
+    /// are done.
+    pub fn run<F, P>(&self, mut solve: F, mut progress: P) -> Result<(), Error>
+    where
+        F: FnMut(&Configuration, &mut Xoshiro256Plus) -> Result<Run, Error>,
+        P: FnMut(&Configuration, &Summary),
+    {
+        fs::create_dir_all(&self.output)?;
+
+        let seeds = self.seeds();
+        self.write_manifest(&seeds)?;
+
+        let mut csv = BufWriter::new(File::create(self.output.join("results.csv"))?);
+        writeln!(csv, "problem,algorithm,run,fitness,iterations,evaluations")?;
+
+        let mut fitnesses = Vec::with_capacity(self.configurations.len());
+        let mut histories = Vec::with_capacity(self.configurations.len());
+
+        for configuration in &self.configurations {
+            let label = configuration.label();
+
+            let mut values = Vec::with_capacity(self.runs);
+            let mut runs = Vec::with_capacity(self.runs);
+
+            for (i, seed) in seeds.iter().enumerate() {
+                let run = solve(configuration, &mut seed.clone())?;
+
+                writeln!(
+                    csv,
+                    "{},{},{i},{:?},{},{}",
+                    csv_field(&configuration.problem),
+                    csv_field(&label),
+                    run.fitness,
+                    run.iterations,
+                    run.evaluations
+                )?;
+
+                values.push(run.fitness);
+                runs.push(run.history);
+            }
+            csv.flush()?;
+
+            progress(configuration, &Summary::new(&values));
+
+            fitnesses.push(values);
+            histories.push(runs);
+        }
+
+        let per_problem = self.configurations.len() / self.problems.len();
+        let labels = self.configurations[..per_problem]
+            .iter()
+            .map(|c| c.label())
+            .collect::<Vec<_>>();
+
+        for (problem, runs) in self.problems.iter().zip(histories.chunks(per_problem)) {
+            let series = labels
+                .iter()
+                .zip(runs)
+                .map(|(label, runs)| Series {
+                    label: label.clone(),
+                    runs: runs.to_vec(),
+                })
+                .collect::<Vec<_>>();
+
+            if series
+                .iter()
+                .flat_map(|s| &s.runs)
+                .any(|run| !run.is_empty())
+            {
+                let path = self.output.join(format!("{problem}.svg"));
+                plot::convergence(BufWriter::new(File::create(path)?), problem, &series)?;
+            }
+        }
+
+        let results = fitnesses
+            .chunks(per_problem)
+            .map(|row| row.to_vec())
+            .collect::<Vec<_>>();
+
+        let report = Report::new(
+            &labels.iter().map(String::as_str).collect::<Vec<_>>(),
+            &self.problems.iter().map(String::as_str).collect::<Vec<_>>(),
+            &results,
+            self.baseline,
+            self.alpha,
+            self.statistic,
+        );
+
+        report.write_markdown(BufWriter::new(File::create(self.output.join("report.md"))?))?;
+        report.write_latex(
+            BufWriter::new(File::create(self.output.join("report.tex"))?),
+            &self.name,
+            &format!("tab:{}", self.name),
+        )?;
+
+        Ok(())
+    }
+
+    fn write_manifest(&self, seeds: &[Xoshiro256Plus]) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(self.output.join("manifest.toml"))?);
+
+        writeln!(
+            writer,
+            "# Run `i` of every configuration starts from `seeds[i]`, and `config`"
+        )?;
+        writeln!(writer, "# is the exact text of the experiment file.")?;
+        writeln!(writer, "experiment = \"{}\"", config::escape(&self.name))?;
+        writeln!(writer, "version = \"{}\"", env!("CARGO_PKG_VERSION"))?;
+        writeln!(writer, "configurations = {}", self.configurations.len())?;
+        writeln!(writer, "runs = {}", self.runs)?;
+
+        writeln!(writer, "seeds = [")?;
+        for seed in seeds {
+            writeln!(writer, "    \"{seed}\",")?;
+        }
+        writeln!(writer, "]")?;
+
+        writeln!(writer, "config = \"{}\"", config::escape(&self.text))?;
+
+        writer.flush()
+    }
+}
+
+// the configurations of the grid of `algorithm` on `problem`
+fn expand(
+    algorithm: &Entry,
+    problem: &str,
+    termination: Termination,
+) -> Result<Vec<Configuration>, Error> {
+    let table = table(algorithm)?;
+
+    let kind =
+        string(table.get("algorithm").ok_or_else(|| {
+            invalid(algorithm, format!("{} is missing algorithm", algorithm.key))
+        })?)?;
+
+    let overrides = match table.get(problem) {
+        Some(entry) => Some(self::table(entry)?),
+        None => None,
+    };
+
+    let mut axes = Vec::new();
+    let mut grid = Vec::new();
+    for entry in &table.entries {
+        match &entry.value {
+            _ if entry.key == "algorithm" => {}
+            Value::Table(_) => {}
+            Value::Array(values) => {
+                if values.is_empty() {
+                    return Err(invalid(
+                        entry,
+                        format!("the grid of {} is empty", entry.key),
+                    ));
+                }
+                if let Some(entry) = overrides.and_then(|o| o.get(&entry.key)) {
+                    return Err(invalid(
+                        entry,
+                        "parameters of the grid cannot be overridden",
+                    ));
+                }
+
+                axes.push((entry.key.clone(), values.clone()));
+                grid.push(entry.key.clone());
+            }
+            value => axes.push((entry.key.clone(), vec![value.clone()])),
+        }
+    }
+
+    for entry in overrides.iter().flat_map(|o| &o.entries) {
+        match &entry.value {
+            Value::Array(_) | Value::Table(_) => {
+                return Err(invalid(entry, "overrides cannot be arrays or tables"));
+            }
+            value => match axes.iter_mut().find(|(key, _)| *key == entry.key) {
+                Some((_, values)) => *values = vec![value.clone()],
+                None => axes.push((entry.key.clone(), vec![value.clone()])),
+            },
+        }
+    }
+
+    // odometer over the axes, with the last one turning fastest
+    let mut configurations = Vec::new();
+    let mut indexes = vec![0; axes.len()];
+    loop {
+        configurations.push(Configuration {
+            problem: problem.to_string(),
+            name: algorithm.key.clone(),
+            algorithm: kind.to_string(),
+            parameters: axes
+                .iter()
+                .zip(&indexes)
+                .map(|((key, values), &i)| (key.clone(), values[i].clone()))
+                .collect(),
+            termination,
+            grid: grid.clone(),
+        });
+
+        let mut axis = axes.len();
+        loop {
+            if axis == 0 {
+                return Ok(configurations);
+            }
+            axis -= 1;
+
+            indexes[axis] += 1;
+            if indexes[axis] < axes[axis].1.len() {
+                break;
+            }
+            indexes[axis] = 0;
+        }
+    }
+}
+
+// the budget of `problem`, from the defaults overridden by its own table
+fn termination(budget: &Table, problem: &str) -> Result<Termination, Error> {
+    let mut termination = Termination {
+        max_iterations: usize::MAX,
+        max_evaluations: usize::MAX,
+        target: f64::NEG_INFINITY,
+    };
+
+    let overrides = match budget.get(problem) {
+        Some(entry) => table(entry)?.entries.iter().collect(),
+        None => Vec::new(),
+    };
+
+    for entry in budget.entries.iter().chain(overrides) {
+        match entry.key.as_str() {
+            "iterations" => termination.max_iterations = integer(entry)?,
+            "evaluations" => termination.max_evaluations = integer(entry)?,
+            "target" => {
+                termination.target = match entry.value {
+                    Value::Float(x) => x,
+                    Value::Integer(n) => n as f64,
+                    _ => return Err(invalid(entry, "target must be a number")),
+                }
+            }
+            _ if matches!(entry.value, Value::Table(_)) => {}
+            key => return Err(invalid(entry, format!("unknown budget {key}"))),
+        }
+    }
+
+    if termination.max_iterations == usize::MAX && termination.max_evaluations == usize::MAX {
+        return Err(Error::Invalid(format!(
+            "the budget of {problem} needs iterations or evaluations"
+        )));
+    }
+
+    Ok(termination)
+}
+
+// subtables of `table` must be named after problems
+fn check_problems(table: &Table, problems: &[String]) -> Result<(), Error> {
+    for entry in &table.entries {
+        if matches!(entry.value, Value::Table(_)) && !problems.contains(&entry.key) {
+            return Err(invalid(entry, format!("unknown problem {}", entry.key)));
+        }
+    }
+    Ok(())
+}
+
+fn check_keys(table: &Table, keys: &[&str]) -> Result<(), Error> {
+    for entry in &table.entries {
+        if !keys.contains(&entry.key.as_str()) {
+            return Err(invalid(entry, format!("unknown key {}", entry.key)));
+        }
+    }
+    Ok(())
+}
+
+fn required<'a>(table: &'a Table, key: &str) -> Result<&'a Entry, Error> {
+    table
+        .get(key)
+        .ok_or_else(|| Error::Invalid(format!("missing key {key}")))
+}
+
+fn invalid(entry: &Entry, message: impl Into<String>) -> Error {
+    parse::Error::at(entry.line, message).into()
+}
+
+fn string(entry: &Entry) -> Result<&str, Error> {
+    match &entry.value {
+        Value::String(s) => Ok(s),
+        value => Err(invalid(
+            entry,
+            format!("{} must be a string, found {value}", entry.key),
+        )),
+    }
+}
+
+fn integer(entry: &Entry) -> Result<usize, Error> {
+    match entry.value {
+        Value::Integer(n) if n >= 0 => Ok(n as usize),
+        ref value => Err(invalid(
+            entry,
+            format!(
+                "{} must be a non negative integer, found {value}",
+                entry.key
+            ),
+        )),
+    }
+}
+
+fn table(entry: &Entry) -> Result<&Table, Error> {
+    match &entry.value {
+        Value::Table(table) => Ok(table),
+        value => Err(invalid(
+            entry,
+            format!("{} must be a table, found {}", entry.key, value.type_name()),
+        )),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"
+name = "test"
+output = "results/test"
+runs = 3
+seed = "xoshiro256+:93920339ac7730ac-8db68f4acc7c22b1-8b804df6a99a1289-ff5fa2f037375aa9"
+problems = ["A", "B"]
+baseline = "de"
+
+[budget]
+iterations = 100
+
+[budget.B]
+evaluations = 5000
+
+[algorithms.ga]
+algorithm = "genetic_algorithm"
+population_size = 20
+mutation_probability = [0.2, 0.1]
+
+[algorithms.ga.B]
+population_size = 60
+
+[algorithms.de]
+algorithm = "differential_evolution"
+crossover_probability = [0.9, 0.8]
+differential_weight = [0.8, 0.5]
+"#;
+
+    #[test]
+    fn grids_are_expanded() {
+        let experiment = Experiment::parse(TEXT).unwrap();
+        let configurations = &experiment.configurations;
+
+        assert_eq!(configurations.len(), 2 * (2 + 4));
+        assert_eq!(experiment.baseline, 2);
+
+        let labels = configurations.iter().map(|c| c.label()).collect::<Vec<_>>();
+        assert_eq!(labels[..6], labels[6..]);
+        assert_eq!(labels[1], "ga(mutation_probability=0.1)");
+        assert_eq!(
+            labels[3],
+            "de(crossover_probability=0.9, differential_weight=0.5)"
+        );
+        assert_eq!(
+            labels[4],
+            "de(crossover_probability=0.8, differential_weight=0.8)"
+        );
+
+        let (a, b) = (&configurations[0], &configurations[6]);
+        assert_eq!(a.usize("population_size").unwrap(), 20);
+        assert_eq!(b.usize("population_size").unwrap(), 60);
+        assert_eq!(b.f64("mutation_probability").unwrap(), 0.2);
+        assert!(a.f64("population_size").is_ok() && a.str("population_size").is_err());
+
+        assert_eq!(a.termination.max_iterations, 100);
+        assert_eq!(a.termination.max_evaluations, usize::MAX);
+        assert_eq!(b.termination.max_iterations, 100);
+        assert_eq!(b.termination.max_evaluations, 5000);
+
+        let seeds = experiment.seeds();
+        let mut second = experiment.seed.clone();
+        second.jump();
+        assert_eq!(seeds[0], experiment.seed);
+        assert_eq!(seeds[1], second);
+    }
+
+    #[test]
+    fn errors_report_the_line() {
+        let cases = [
+            ("runs = 3", "runs = 0", 4),
+            ("problems = [\"A\", \"B\"]", "problems = \"A\"", 6),
+            ("[budget.B]", "[budget.C]", 12),
+            ("population_size = 60", "mutation_probability = 0.3", 21),
+            ("iterations = 100", "iteration = 100", 10),
+            ("baseline = \"de\"", "baseline = \"pso\"", 7),
+        ];
+
+        let cases = cases.map(|(from, to, line)| (TEXT.replace(from, to), line));
+        parse::assert_error_lines(cases, |text| match Experiment::parse(text) {
+            Err(Error::Read(error)) => Err(error),
+            Err(error) => panic!("{text:?} failed with {error}"),
+            Ok(_) => Ok(()),
+        });
+
+        let text = TEXT.replace("seed = ", "# seed = ");
+        assert!(matches!(Experiment::parse(&text), Err(Error::Invalid(_))));
+    }
+}
@@ -0,0 +1,516 @@
+//! Automatic configuration of the parameters of an algorithm by iterated
+//! racing, in the manner of irace.
+//!
+//! Every iteration samples candidate configurations, around the elites of
+//! the previous iteration once there are some, and races them: the
+//! candidates are run on one instance after another, and after
+//! `first_test` instances a Friedman test over the instances seen so far
+//! eliminates those found worse than the best one. The survivors become the
+//! elites of the next iteration.
+//!
+//! The instances are typically the configurations of one algorithm in an
+//! [`Experiment`](super::Experiment), one per problem, and are solved as in
+//! [`Experiment::run`](super::Experiment::run).
+
+use rand::distributions::{Normal, UniformClosedOpen};
+use rand::rngs::{Rng, Xoshiro256Plus};
+
+use super::{Configuration, Error, Run, Value};
+use crate::metaheuristics::Range;
+use crate::stats::{friedman, Friedman};
+
+/// The values a parameter may take.
+#[derive(Clone, Debug)]
+pub enum Domain {
+    Real(Range<f64>),
+    /// Positive reals sampled uniformly on a logarithmic scale, for
+    /// parameters spanning several orders of magnitude.
+    LogReal(Range<f64>),
+    /// Integers from `start` to `end`, both included.
+    Integer(Range<i64>),
+    Categorical(Vec<Value>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pub name: String,
+    pub domain: Domain,
+}
+
+impl Parameter {
+    pub fn new(name: &str, domain: Domain) -> Self {
+        Self {
+            name: name.to_string(),
+            domain,
+        }
+    }
+}
+
+pub struct Racing {
+    pub parameters: Vec<Parameter>,
+    /// Number of evaluations of the problems that tuning may spend, summed
+    /// over the runs of the configurations on the instances.
+    ///
+    /// A step of a race only starts if its runs are expected to fit in what
+    /// is left, at the mean cost of the runs so far or, before the first one,
+    /// at the evaluation limits of the instances. The budget can thus be
+    /// overrun by the error of that estimate over a single step, and the
+    /// very first step always starts.
+    pub budget: usize,
+    /// Instances every candidate is run on before the first test.
+    pub first_test: usize,
+    /// Significance level of the tests that eliminate candidates.
+    pub alpha: f64,
+}
+
+/// A configuration that survived the last race.
+#[derive(Clone, Debug)]
+pub struct Elite {
+    pub parameters: Vec<(String, Value)>,
+    /// Mean rank among the survivors over the instances of the last race,
+    /// where rank 1 is the best.
+    pub mean_rank: f64,
+    /// Number of instances it was run on.
+    pub instances: usize,
+}
+
+// a sampled configuration and its results, one per instance, in order
+struct Candidate {
+    values: Vec<Value>,
+    results: Vec<f64>,
+    mean_rank: f64,
+}
+
+/// Tunes `racing.parameters` and returns the elite configurations, best
+/// first.
+///
+/// Instance `k` is `instances[k % instances.len()]`, which supplies the
+/// problem, the budget and the parameters that are not tuned, run from a
+/// seed of its own that `rng` also provides. Every pass over `instances`
+/// uses new seeds.
+pub fn race<F>(
+    rng: &mut Xoshiro256Plus,
+    racing: &Racing,
+    instances: &[Configuration],
+    mut solve: F,
+) -> Result<Vec<Elite>, Error>
+where
+    F: FnMut(&Configuration, &mut Xoshiro256Plus) -> Result<Run, Error>,
+{
+    assert!(!instances.is_empty(), "there must be at least one instance");
+    assert!(
+        !racing.parameters.is_empty(),
+        "there must be parameters to tune"
+    );
+
+    let d = racing.parameters.len() as f64;
+    let iterations = (2.0 + d.log2()).floor() as usize;
+    let survivors = iterations;
+
+    // seeds of the passes over the instances, in a stream apart from the
+    // one of the sampling
+    let mut stream = rng.clone();
+    rng.long_jump();
+    let mut seeds = Vec::new();
+
+    let mut elites = Vec::<Candidate>::new();
+    let mut used = 0;
+    let mut runs = 0;
+
+    // evaluations a run is expected to cost, from the limits of the
+    // instances until there are runs to average
+    let mut cost = instances
+        .iter()
+        .map(|instance| instance.termination.max_evaluations as f64)
+        .sum::<f64>()
+        / instances.len() as f64;
+
+    for iteration in 0.. {
+        // what is left is split evenly over the iterations still planned
+        let left = racing.budget.saturating_sub(used);
+        let budget = left / iterations.saturating_sub(iteration).max(1);
+        let runs_per_candidate = (racing.first_test + iteration.min(5)) as f64;
+        let size = ((budget as f64 / cost / runs_per_candidate) as usize).max(survivors + 1);
+
+        let mut candidates = elites;
+        let elite_count = candidates.len();
+        let mut attempts = 0;
+        while candidates.len() < size && attempts < 100 * size {
+            attempts += 1;
+
+            let values = if elite_count == 0 {
+                uniform(rng, &racing.parameters)
+            } else {
+                let parent = parent(rng, elite_count);
+                let spread = (1.0 / size as f64).powf(iteration as f64 / d);
+                around(rng, &racing.parameters, &candidates[parent].values, spread)
+            };
+
+            if candidates.iter().all(|c| c.values != values) {
+                candidates.push(Candidate {
+                    values,
+                    results: Vec::new(),
+                    mean_rank: 1.0,
+                });
+            }
+        }
+
+        if candidates.len() == elite_count {
+            elites = candidates;
+            break;
+        }
+
+        let mut alive = (0..candidates.len()).collect::<Vec<_>>();
+        let mut spent = 0;
+
+        for k in 0.. {
+            let missing = alive
+                .iter()
+                .filter(|&&c| candidates[c].results.len() <= k)
+                .count();
+            let expected = missing as f64 * cost;
+            if runs > 0
+                && ((used + spent) as f64 + expected > racing.budget as f64
+                    || spent as f64 + expected > budget as f64)
+            {
+                break;
+            }
+
+            let instance = &instances[k % instances.len()];
+            while seeds.len() <= k / instances.len() {
+                seeds.push(stream.clone());
+                stream.jump();
+            }
+
+            for &c in &alive {
+                let candidate = &mut candidates[c];
+                if candidate.results.len() <= k {
+                    let configuration = configure(instance, &racing.parameters, &candidate.values);
+                    let run = solve(&configuration, &mut seeds[k / instances.len()].clone())?;
+                    candidate.results.push(run.fitness);
+                    spent += run.evaluations;
+                }
+            }
+            runs += missing;
+            cost = (used + spent) as f64 / runs as f64;
+
+            if k + 1 >= racing.first_test && alive.len() >= 2 {
+                let test = test(&candidates, &alive);
+
+                if test.p_value < racing.alpha {
+                    let best = (0..alive.len())
+                        .min_by(|&a, &b| test.mean_ranks[a].total_cmp(&test.mean_ranks[b]))
+                        .unwrap();
+                    let p_values = &test.holm()[best];
+
+                    alive = alive
+                        .iter()
+                        .zip(p_values)
+                        .filter(|&(_, &p)| p >= racing.alpha)
+                        .map(|(&c, _)| c)
+                        .collect();
+                }
+            }
+
+            if alive.len() <= survivors {
+                break;
+            }
+        }
+
+        // candidates the budget did not allow to run are left out
+        alive.retain(|&c| !candidates[c].results.is_empty());
+
+        if alive.len() >= 2 {
+            let test = test(&candidates, &alive);
+            for (&c, &rank) in alive.iter().zip(&test.mean_ranks) {
+                candidates[c].mean_rank = rank;
+            }
+        } else if let [c] = alive[..] {
+            candidates[c].mean_rank = 1.0;
+        }
+
+        alive.sort_by(|&a, &b| candidates[a].mean_rank.total_cmp(&candidates[b].mean_rank));
+        alive.truncate(survivors);
+
+        let mut candidates = candidates.into_iter().map(Some).collect::<Vec<_>>();
+        elites = alive
+            .iter()
+            .map(|&c| candidates[c].take().unwrap())
+            .collect();
+
+        if spent == 0 {
+            break;
+        }
+        used += spent;
+    }
+
+    Ok(elites
+        .into_iter()
+        .map(|elite| Elite {
+            parameters: names(&racing.parameters).zip(elite.values).collect(),
+            mean_rank: elite.mean_rank,
+            instances: elite.results.len(),
+        })
+        .collect())
+}
+
+// Friedman test of the `alive` candidates over the instances they all have
+// results on
+fn test(candidates: &[Candidate], alive: &[usize]) -> Friedman {
+    let instances = alive
+        .iter()
+        .map(|&c| candidates[c].results.len())
+        .min()
+        .unwrap();
+
+    let data = (0..instances)
+        .map(|i| alive.iter().map(|&c| candidates[c].results[i]).collect())
+        .collect::<Vec<_>>();
+
+    friedman(&data)
+}
+
+fn names(parameters: &[Parameter]) -> impl Iterator<Item = String> + '_ {
+    parameters.iter().map(|p| p.name.clone())
+}
+
+// `instance` with the tuned parameters set to `values`
+fn configure(
+    instance: &Configuration,
+    parameters: &[Parameter],
+    values: &[Value],
+) -> Configuration {
+    let mut configuration = instance.clone();
+
+    for (name, value) in names(parameters).zip(values) {
+        match configuration
+            .parameters
+            .iter_mut()
+            .find(|(key, _)| *key == name)
+        {
+            Some((_, v)) => *v = value.clone(),
+            None => configuration.parameters.push((name, value.clone())),
+        }
+    }
+    configuration.grid = names(parameters).collect();
+
+    configuration
+}
+
+fn uniform(rng: &mut Xoshiro256Plus, parameters: &[Parameter]) -> Vec<Value> {
+    parameters
+        .iter()
+        .map(|parameter| match &parameter.domain {
+            Domain::Real(range) => {
+                Value::Float(rng.sample(&UniformClosedOpen::new(range.start, range.end)))
+            }
+            Domain::LogReal(range) => Value::Float(f64::exp(
+                rng.sample(&UniformClosedOpen::new(range.start.ln(), range.end.ln())),
+            )),
+            Domain::Integer(range) => Value::Integer(
+                rng.sample(&UniformClosedOpen::new(
+                    range.start as f64,
+                    (range.end + 1) as f64,
+                ))
+                .floor() as i64,
+            ),
+            Domain::Categorical(values) => {
+                values[rng.sample(&UniformClosedOpen::new(0, values.len()))].clone()
+            }
+        })
+        .collect()
+}
+
+// index of an elite, drawn with weights decreasing linearly with its rank
+fn parent(rng: &mut Xoshiro256Plus, elites: usize) -> usize {
+    let total = (elites * (elites + 1) / 2) as f64;
+    let mut u = rng.sample(&UniformClosedOpen::new(0.0, total));
+
+    for i in 0..elites {
+        let weight = (elites - i) as f64;
+        if u < weight {
+            return i;
+        }
+        u -= weight;
+    }
+    elites - 1
+}
+
+// a configuration near `parent`: numbers are drawn from a normal distribution
+// around its values, truncated to their domain, with a standard deviation of
+// `spread` times its width, and categories keep the value of the parent with
+// a probability that grows as `spread` shrinks
+fn around(
+    rng: &mut Xoshiro256Plus,
+    parameters: &[Parameter],
+    parent: &[Value],
+    spread: f64,
+) -> Vec<Value> {
+    parameters
+        .iter()
+        .zip(parent)
+        .map(|(parameter, value)| match (&parameter.domain, value) {
+            (Domain::Real(range), &Value::Float(x)) => {
+                Value::Float(truncated_normal(rng, x, spread, range.start, range.end))
+            }
+            (Domain::LogReal(range), &Value::Float(x)) => Value::Float(f64::exp(truncated_normal(
+                rng,
+                x.ln(),
+                spread,
+                range.start.ln(),
+                range.end.ln(),
+            ))),
+            (Domain::Integer(range), &Value::Integer(n)) => {
+                let (start, end) = (range.start as f64, (range.end + 1) as f64);
+                let x = truncated_normal(rng, n as f64 + 0.5, spread, start, end);
+                Value::Integer((x.floor() as i64).min(range.end))
+            }
+            (Domain::Categorical(values), value) => {
+                let keep = 1.0 - spread * (1.0 - 1.0 / values.len() as f64);
+                if rng.sample(&UniformClosedOpen::new(0.0, 1.0)) < keep {
+                    value.clone()
+                } else {
+                    values[rng.sample(&UniformClosedOpen::new(0, values.len()))].clone()
+                }
+            }
+            _ => unreachable!("values are sampled from their domain"),
+        })
+        .collect()
+}
+
+// rejection sampling, falling back on clamping
+fn truncated_normal(rng: &mut Xoshiro256Plus, mean: f64, spread: f64, start: f64, end: f64) -> f64 {
+    let normal = Normal::new(mean, spread * (end - start));
+
+    for _ in 0..100 {
+        let x = rng.sample(&normal);
+        if start <= x && x < end {
+            return x;
+        }
+    }
+    mean.clamp(start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::Termination;
+
+    #[test]
+    fn race_finds_good_configurations() {
+        let instance = Configuration {
+            problem: "noisy".to_string(),
+            name: "toy".to_string(),
+            algorithm: "toy".to_string(),
+            parameters: vec![("offset".to_string(), Value::Float(0.0))],
+            termination: Termination::evaluations(20),
+            grid: Vec::new(),
+        };
+
+        let racing = Racing {
+            parameters: vec![
+                Parameter::new("x", Domain::Real(Range::new(0.0, 1.0))),
+                Parameter::new("n", Domain::Integer(Range { start: 1, end: 10 })),
+                Parameter::new(
+                    "kind",
+                    Domain::Categorical(vec![
+                        Value::String("good".to_string()),
+                        Value::String("bad".to_string()),
+                    ]),
+                ),
+            ],
+            budget: 20_000,
+            first_test: 5,
+            alpha: 0.05,
+        };
+
+        let mut evaluations = 0;
+        let solve = |configuration: &Configuration, rng: &mut Xoshiro256Plus| {
+            evaluations += 20;
+
+            let x = configuration.f64("x")?;
+            let n = configuration.usize("n")? as f64;
+            let penalty = if configuration.str("kind")? == "good" {
+                0.0
+            } else {
+                1.0
+            };
+            let noise = rng.sample(&Normal::new(0.0, 0.05));
+
+            Ok(Run {
+                fitness: (x - 0.3).powi(2) + (n - 7.0).powi(2) / 100.0 + penalty + noise,
+                iterations: 1,
+                evaluations: 20,
+                history: Vec::new(),
+            })
+        };
+
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let elites = race(&mut rng, &racing, &[instance], solve).unwrap();
+
+        assert!(evaluations <= racing.budget);
+        assert!(!elites.is_empty());
+
+        let best = &elites[0].parameters;
+        assert_eq!(best[2].1, Value::String("good".to_string()));
+        let Value::Float(x) = best[0].1 else {
+            panic!("x is not a float");
+        };
+        assert!((x - 0.3).abs() < 0.15, "{x}");
+        let Value::Integer(n) = best[1].1 else {
+            panic!("n is not an integer");
+        };
+        assert!((n - 7).abs() <= 2, "{n}");
+    }
+
+    #[test]
+    fn races_are_charged_evaluations() {
+        // the instance does not limit evaluations, and runs cost more the
+        // larger `n` is
+        let instance = Configuration {
+            problem: "costly".to_string(),
+            name: "toy".to_string(),
+            algorithm: "toy".to_string(),
+            parameters: Vec::new(),
+            termination: Termination::iterations(10),
+            grid: Vec::new(),
+        };
+        let racing = Racing {
+            parameters: vec![Parameter::new(
+                "n",
+                Domain::Integer(Range { start: 1, end: 100 }),
+            )],
+            budget: 50_000,
+            first_test: 5,
+            alpha: 0.05,
+        };
+
+        let mut runs = 0;
+        let mut evaluations = 0;
+        let solve = |configuration: &Configuration, rng: &mut Xoshiro256Plus| {
+            let n = configuration.usize("n")?;
+            runs += 1;
+            evaluations += 10 * n;
+
+            Ok(Run {
+                fitness: (n as f64 - 50.0).abs() + rng.sample(&Normal::new(0.0, 1.0)),
+                iterations: 10,
+                evaluations: 10 * n,
+                history: Vec::new(),
+            })
+        };
+
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let elites = race(&mut rng, &racing, &[instance], solve).unwrap();
+
+        // far fewer runs than the budget, which only overruns by a step
+        assert!(runs < racing.budget / 100, "{runs}");
+        assert!(evaluations <= racing.budget * 11 / 10, "{evaluations}");
+        assert!(evaluations >= racing.budget * 3 / 4, "{evaluations}");
+
+        let Value::Integer(n) = elites[0].parameters[0].1 else {
+            panic!("n is not an integer");
+        };
+        assert!((n - 50).abs() <= 10, "{n}");
+    }
+}
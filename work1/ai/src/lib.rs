@@ -1 +1,6 @@
+pub mod experiment;
 pub mod metaheuristics;
+pub mod parse;
+pub mod plot;
+pub mod report;
+pub mod stats;
@@ -1,8 +1,9 @@
-#![feature(generic_arg_infer)]
-
 use std::f64::consts::{PI, TAU};
 
-use ai::metaheuristics::{evolutionary_computation::*, Problem, Range};
+use ai::experiment::{Configuration, Error, Experiment, Run};
+use ai::metaheuristics::{
+    evolutionary_computation::*, observer::History, swarm_intelligence::*, Problem, Range,
+};
 
 use rand::rngs::Xoshiro256Plus;
 
@@ -102,199 +103,270 @@ impl Problem<6> for FMSound {
 }
 
 fn main() {
-    let number_of_runs = 30;
-
-    let seed = [
-        0x93920339ac7730ac,
-        0x8db68f4acc7c22b1,
-        0x8b804df6a99a1289,
-        0xff5fa2f037375aa9,
-    ];
-
-    let mut results = Vec::with_capacity(number_of_runs);
-
-    for i in 0..number_of_runs {
-        let mut rng = Xoshiro256Plus::new(seed);
-        for _ in 0..i {
-            rng.jump();
-        }
-
-        // uncomment the desired combination of algorithm/problem
-        let result = {
-            genetic_algorithm::<_, TubularColumn, _>(
-                &mut rng,
-                1000,
-                20,
-                2,
-                0.9,
-                0.2,
-            )
-            // genetic_algorithm::<_, TubularColumn, _>(
-            //     &mut rng,
-            //     1000,
-            //     20,
-            //     2,
-            //     0.9,
-            //     0.15,
-            // )
-            // genetic_algorithm::<_, TubularColumn, _>(
-            //     &mut rng,
-            //     1000,
-            //     20,
-            //     2,
-            //     0.9,
-            //     0.1,
-            // )
-            // differential_evolution::<_, TubularColumn, _>(
-            //     &mut rng,
-            //     1000,
-            //     20,
-            //     0.9,
-            //     0.8,
-            // )
-            // differential_evolution::<_, TubularColumn, _>(
-            //     &mut rng,
-            //     1000,
-            //     20,
-            //     0.9,
-            //     0.5,
-            // )
-            // differential_evolution::<_, TubularColumn, _>(
-            //     &mut rng,
-            //     1000,
-            //     20,
-            //     0.8,
-            //     0.5,
-            // )
-
-            // genetic_algorithm::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     3,
-            //     0.9,
-            //     0.2,
-            // )
-            // genetic_algorithm::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     3,
-            //     0.9,
-            //     0.15,
-            // )
-            // genetic_algorithm::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     3,
-            //     0.9,
-            //     0.1,
-            // )
-            // differential_evolution::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     0.9,
-            //     0.8,
-            // )
-            // differential_evolution::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     0.9,
-            //     0.5,
-            // )
-            // differential_evolution::<_, TensionCompressionSpring, _>(
-            //     &mut rng,
-            //     1000,
-            //     30,
-            //     0.8,
-            //     0.5,
-            // )
-
-            // genetic_algorithm::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     6,
-            //     0.9,
-            //     0.2,
-            // )
-            // genetic_algorithm::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     6,
-            //     0.9,
-            //     0.15,
-            // )
-            // genetic_algorithm::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     6,
-            //     0.9,
-            //     0.1,
-            // )
-            // differential_evolution::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     0.9,
-            //     0.8,
-            // )
-            // differential_evolution::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     0.9,
-            //     0.5,
-            // )
-            // differential_evolution::<_, FMSound, _>(
-            //     &mut rng,
-            //     10000,
-            //     60,
-            //     0.8,
-            //     0.5,
-            // )
-        };
-
-        // uncomment the line with the desired problem
-        results.push(TubularColumn::f(&result));
-        // results.push(TensionCompressionSpring::f(&result));
-        // results.push(FMSound::f(&result));
+    let path = std::env::args().nth(1).unwrap_or_else(|| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/experiments/engineering.toml").to_string()
+    });
+
+    let result = Experiment::load(&path).and_then(|experiment| {
+        experiment.run(
+            |configuration, rng| match configuration.problem.as_str() {
+                "TubularColumn" => solve::<TubularColumn, 2>(configuration, rng),
+                "TensionCompressionSpring" => {
+                    solve::<TensionCompressionSpring, 3>(configuration, rng)
+                }
+                "FMSound" => solve::<FMSound, 6>(configuration, rng),
+                problem => Err(Error::Invalid(format!("unknown problem {problem}"))),
+            },
+            |configuration, summary| {
+                eprintln!(
+                    "{} {}: median {:e}, IQR {:e}",
+                    configuration.problem,
+                    configuration.label(),
+                    summary.median,
+                    summary.iqr()
+                )
+            },
+        )?;
+
+        eprintln!("results written to {}", experiment.output.display());
+        Ok(())
+    });
+
+    if let Err(error) = result {
+        eprintln!("{path}: {error}");
+        std::process::exit(1);
     }
+}
 
-    results.sort_by(|a, b| a.partial_cmp(b).unwrap());
+fn solve<P, const D: usize>(
+    configuration: &Configuration,
+    rng: &mut Xoshiro256Plus,
+) -> Result<Run, Error>
+where
+    P: Problem<D>,
+{
+    let mut history = History::new();
+
+    let solution = match configuration.algorithm.as_str() {
+        "genetic_algorithm" => genetic_algorithm::<_, P, _, _, _, _, D>(
+            rng,
+            configuration.termination,
+            &GeneticAlgorithm {
+                population_size: configuration.usize("population_size")?,
+                selection: Tournament::new(configuration.usize("tournament_size")?),
+                crossover: Arithmetic,
+                crossover_probability: configuration.f64("crossover_probability")?,
+                mutation: UniformReset::new(configuration.f64("mutation_probability")?),
+                replacement: Replacement::Generational { elitism: 0 },
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "island_model" => island_model::<P, _, _, D>(
+            rng,
+            configuration.termination,
+            &IslandModel {
+                algorithm: GeneticAlgorithm {
+                    population_size: configuration.usize("population_size")?,
+                    selection: Tournament::new(configuration.usize("tournament_size")?),
+                    crossover: Arithmetic,
+                    crossover_probability: configuration.f64("crossover_probability")?,
+                    mutation: UniformReset::new(configuration.f64("mutation_probability")?),
+                    replacement: Replacement::Generational { elitism: 0 },
+                    workers: 1,
+                },
+                islands: configuration.usize("islands")?,
+                migration_interval: configuration.usize("migration_interval")?,
+                number_of_migrants: configuration.usize("number_of_migrants")?,
+                topology: topology(configuration)?,
+                migrant_selection: MigrantSelection::Best,
+                migrant_replacement: MigrantReplacement::Worst,
+            },
+            &mut history,
+        ),
+        "differential_evolution" => differential_evolution::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &DifferentialEvolution {
+                population_size: configuration.usize("population_size")?,
+                crossover_probability: configuration.f64("crossover_probability")?,
+                differential_weight: configuration.f64("differential_weight")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "artificial_bee_colony" => artificial_bee_colony::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &ArtificialBeeColony {
+                colony_size: configuration.usize("colony_size")?,
+                limit: configuration.usize("limit")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "constrained_artificial_bee_colony" => constrained_artificial_bee_colony::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &ConstrainedArtificialBeeColony {
+                colony_size: configuration.usize("colony_size")?,
+                limit: configuration.usize("limit")?,
+                modification_rate: configuration.f64("modification_rate")?,
+                scout_production_period: configuration.usize("scout_production_period")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "cuckoo_search" => cuckoo_search::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &CuckooSearch {
+                population_size: configuration.usize("population_size")?,
+                abandon_probability: configuration.f64("abandon_probability")?,
+                beta: configuration.f64("beta")?,
+                step_size: configuration.f64("step_size")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "firefly_algorithm" => firefly_algorithm::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &FireflyAlgorithm {
+                population_size: configuration.usize("population_size")?,
+                attractiveness: configuration.f64("attractiveness")?,
+                absorption: configuration.f64("absorption")?,
+                randomization: configuration.f64("randomization")?,
+                randomization_decay: configuration.f64("randomization_decay")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "bat_algorithm" => bat_algorithm::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &BatAlgorithm {
+                population_size: configuration.usize("population_size")?,
+                frequencies: (
+                    configuration.f64("minimum_frequency")?,
+                    configuration.f64("maximum_frequency")?,
+                ),
+                loudness: configuration.f64("loudness")?,
+                loudness_decay: configuration.f64("loudness_decay")?,
+                pulse_rate: configuration.f64("pulse_rate")?,
+                pulse_rate_growth: configuration.f64("pulse_rate_growth")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "continuous_ant_colony" => continuous_ant_colony::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &ContinuousAntColony {
+                archive_size: configuration.usize("archive_size")?,
+                ants: configuration.usize("ants")?,
+                locality: configuration.f64("locality")?,
+                convergence_speed: configuration.f64("convergence_speed")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "grey_wolf_optimizer" => grey_wolf_optimizer::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &GreyWolfOptimizer {
+                population_size: configuration.usize("population_size")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "whale_optimization_algorithm" => whale_optimization_algorithm::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &WhaleOptimizationAlgorithm {
+                population_size: configuration.usize("population_size")?,
+                spiral_shape: configuration.f64("spiral_shape")?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        "particle_swarm" => particle_swarm::<_, P, _, D>(
+            rng,
+            configuration.termination,
+            &ParticleSwarm {
+                population_size: configuration.usize("population_size")?,
+                update: velocity_update(configuration)?,
+                neighborhood: neighborhood(configuration)?,
+                workers: 1,
+            },
+            &mut history,
+        ),
+        algorithm => return Err(Error::Invalid(format!("unknown algorithm {algorithm}"))),
+    };
 
-    let &min = results.first().unwrap();
+    Ok(Run {
+        fitness: P::f(&solution.x),
+        iterations: solution.iterations,
+        evaluations: solution.evaluations,
+        history: history.points,
+    })
+}
 
-    let mean = results.iter().sum::<f64>() / (number_of_runs as f64);
+fn velocity_update(configuration: &Configuration) -> Result<VelocityUpdate, Error> {
+    let update = match configuration.str("update")? {
+        "inertia" => VelocityUpdate::Inertia {
+            inertia: configuration.f64("inertia")?,
+            cognitive: configuration.f64("cognitive")?,
+            social: configuration.f64("social")?,
+        },
+        "constriction" => VelocityUpdate::Constriction {
+            cognitive: configuration.f64("cognitive")?,
+            social: configuration.f64("social")?,
+        },
+        "fully_informed" => VelocityUpdate::FullyInformed {
+            acceleration: configuration.f64("acceleration")?,
+        },
+        "comprehensive_learning" => VelocityUpdate::ComprehensiveLearning {
+            inertia: (
+                configuration.f64("initial_inertia")?,
+                configuration.f64("final_inertia")?,
+            ),
+            acceleration: configuration.f64("acceleration")?,
+            refreshing_gap: configuration.usize("refreshing_gap")?,
+        },
+        "standard_2011" => VelocityUpdate::Standard2011 {
+            inertia: configuration.f64("inertia")?,
+            acceleration: configuration.f64("acceleration")?,
+        },
+        update => return Err(Error::Invalid(format!("unknown velocity update {update}"))),
+    };
+    Ok(update)
+}
 
-    let median = {
-        let half = number_of_runs / 2;
-        if number_of_runs % 2 == 0 {
-            (results[half - 1] + results[half]) / 2.0
-        } else {
-            results[half]
+fn neighborhood(configuration: &Configuration) -> Result<Neighborhood, Error> {
+    let neighborhood = match configuration.str("neighborhood")? {
+        "global" => Neighborhood::Global,
+        "ring" => Neighborhood::Ring,
+        "von_neumann" => Neighborhood::VonNeumann,
+        "random" => Neighborhood::Random {
+            informants: configuration.usize("informants")?,
+        },
+        neighborhood => {
+            return Err(Error::Invalid(format!(
+                "unknown neighborhood {neighborhood}"
+            )))
         }
     };
+    Ok(neighborhood)
+}
 
-    let &max = results.last().unwrap();
-
-    let std_deviation = {
-        let variance = results
-            .iter()
-            .map(|&value| {
-                let diff = mean - value;
-                diff * diff
-            })
-            .sum::<f64>()
-            / (number_of_runs as f64);
-
-        f64::sqrt(variance)
+fn topology(configuration: &Configuration) -> Result<Topology, Error> {
+    let topology = match configuration.str("topology")? {
+        "ring" => Topology::Ring,
+        "fully_connected" => Topology::FullyConnected,
+        "random" => Topology::Random,
+        "star" => Topology::Star,
+        topology => return Err(Error::Invalid(format!("unknown topology {topology}"))),
     };
-
-    dbg!(min, mean, median, max, std_deviation);
+    Ok(topology)
 }
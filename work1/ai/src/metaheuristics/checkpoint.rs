@@ -0,0 +1,388 @@
+//! Saving and restoring the state of a run, so that long runs can be stopped
+//! and resumed later with exactly the same results.
+//!
+//! A checkpoint is a text file made of one record per line:
+//!
+//! ```text
+//! checkpoint 1
+//! rng xoshiro256+:93920339ac7730ac-5c6e3b2a4c58f1c5-2b6e1a1d0e13f4d2-8e2f40b8fc1a6d6b
+//! dimension 2
+//! iterations 250
+//! evaluations 5020
+//! best 3fb999999999999a 3ff0000000000000 c000000000000000
+//! parameters 4059000000000000
+//! individuals 20
+//! 3fb999999999999a 3 3ff0000000000000 c000000000000000
+//! ...
+//! ```
+//!
+//! The generator is stored in its own text encoding and every `f64` as the
+//! hexadecimal representation of its bits. The best point and each individual
+//! start with their fitness, and individuals also store their age.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::observer::Observer;
+use super::{Metaheuristic, Population, Problem, Solution, Termination};
+
+pub use crate::parse::Error;
+
+const VERSION: u32 = 1;
+
+pub fn write<R, W, const D: usize>(
+    mut writer: W,
+    rng: &R,
+    population: &Population<D>,
+) -> io::Result<()>
+where
+    R: fmt::Display,
+    W: Write,
+{
+    writeln!(writer, "checkpoint {VERSION}")?;
+    writeln!(writer, "rng {}", rng)?;
+    writeln!(writer, "dimension {D}")?;
+    writeln!(writer, "iterations {}", population.iterations)?;
+    writeln!(writer, "evaluations {}", population.evaluations)?;
+
+    write!(writer, "best {}", hex(population.best_fitness))?;
+    for &x in &population.best_individual {
+        write!(writer, " {}", hex(x))?;
+    }
+    writeln!(writer)?;
+
+    write!(writer, "parameters")?;
+    for &parameter in &population.parameters {
+        write!(writer, " {}", hex(parameter))?;
+    }
+    writeln!(writer)?;
+
+    writeln!(writer, "individuals {}", population.individuals.len())?;
+    for ((individual, &fitness), age) in population
+        .individuals
+        .iter()
+        .zip(&population.fitnesses)
+        .zip(&population.ages)
+    {
+        write!(writer, "{} {age}", hex(fitness))?;
+        for &x in individual {
+            write!(writer, " {}", hex(x))?;
+        }
+        writeln!(writer)?;
+    }
+
+    writer.flush()
+}
+
+pub fn read<R, B, const D: usize>(reader: B) -> Result<(R, Population<D>), Error>
+where
+    R: FromStr,
+    R::Err: fmt::Display,
+    B: BufRead,
+{
+    let mut lines = Lines {
+        lines: reader.lines(),
+        number: 0,
+    };
+
+    let version = lines.record("checkpoint")?;
+    if version != [VERSION.to_string()] {
+        return Err(lines.error(format!("unsupported version {}", version.join(" "))));
+    }
+
+    let [state] = lines.fields::<1>("rng")?;
+    let rng = state
+        .parse::<R>()
+        .map_err(|error| lines.error(format!("invalid generator: {error}")))?;
+
+    let [dimension] = lines.fields::<1>("dimension")?;
+    if dimension != D.to_string() {
+        return Err(lines.error(format!("expected dimension {D}, found {dimension}")));
+    }
+
+    let [iterations] = lines.fields::<1>("iterations")?;
+    let iterations = lines.integer(&iterations)?;
+
+    let [evaluations] = lines.fields::<1>("evaluations")?;
+    let evaluations = lines.integer(&evaluations)?;
+
+    let best = lines.record("best")?;
+    let (best_fitness, best_individual) = lines.point::<D>(&best)?;
+
+    let parameters = lines
+        .record("parameters")?
+        .iter()
+        .map(|field| lines.float(field))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [size] = lines.fields::<1>("individuals")?;
+    let size = lines.integer(&size)?;
+
+    let mut individuals = Vec::with_capacity(size);
+    let mut fitnesses = Vec::with_capacity(size);
+    let mut ages = Vec::with_capacity(size);
+
+    for _ in 0..size {
+        let mut fields = lines.next()?;
+        if fields.len() != D + 2 {
+            return Err(lines.error(format!("expected {} fields, found {}", D + 2, fields.len())));
+        }
+
+        ages.push(lines.integer(&fields.remove(1))?);
+
+        let (fitness, individual) = lines.point::<D>(&fields)?;
+        fitnesses.push(fitness);
+        individuals.push(individual);
+    }
+
+    let population = Population {
+        individuals,
+        fitnesses,
+        ages,
+        best_individual,
+        best_fitness,
+        iterations,
+        evaluations,
+        parameters,
+    };
+
+    Ok((rng, population))
+}
+
+/// Writes a checkpoint to `path`. The file is replaced atomically, so an
+/// interrupted save leaves the previous checkpoint intact.
+pub fn save<R, const D: usize>(
+    path: impl AsRef<Path>,
+    rng: &R,
+    population: &Population<D>,
+) -> io::Result<()>
+where
+    R: fmt::Display,
+{
+    let path = path.as_ref();
+    let temporary = path.with_extension("tmp");
+
+    write(BufWriter::new(File::create(&temporary)?), rng, population)?;
+    fs::rename(temporary, path)
+}
+
+pub fn load<R, const D: usize>(path: impl AsRef<Path>) -> Result<(R, Population<D>), Error>
+where
+    R: FromStr,
+    R::Err: fmt::Display,
+{
+    read(BufReader::new(File::open(path)?))
+}
+
+/// Like [`run`](super::run), but saves a checkpoint to `path` every
+/// `interval` iterations and once more when the run ends. Passing
+/// what [`load`] returns resumes the run as if it had never stopped.
+pub fn run_with_checkpoints<R, P, E, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    algorithm: &E,
+    mut population: Population<D>,
+    path: impl AsRef<Path>,
+    interval: usize,
+    observer: &mut O,
+) -> io::Result<Solution<D>>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>
+        + fmt::Display,
+    P: Problem<D>,
+    E: Metaheuristic,
+    O: Observer<D>,
+{
+    let path = path.as_ref();
+
+    observer.initialize(&population);
+
+    while !population.is_done(&termination) {
+        algorithm.iterate::<R, P, D>(rng, &mut population, &termination);
+
+        if population.iterations.is_multiple_of(interval) {
+            save(path, rng, &population)?;
+        }
+
+        if observer.generation(&population).is_break() {
+            break;
+        }
+    }
+
+    save(path, rng, &population)?;
+
+    observer.terminate(&population);
+
+    Ok(population.solution())
+}
+
+fn hex(x: f64) -> String {
+    format!("{:016x}", x.to_bits())
+}
+
+struct Lines<B> {
+    lines: io::Lines<B>,
+    number: usize,
+}
+
+impl<B: BufRead> Lines<B> {
+    fn error(&self, message: String) -> Error {
+        Error::at(self.number, message)
+    }
+
+    fn next(&mut self) -> Result<Vec<String>, Error> {
+        self.number += 1;
+
+        match self.lines.next() {
+            Some(line) => Ok(line?.split_whitespace().map(String::from).collect()),
+            None => Err(self.error("unexpected end of file".to_string())),
+        }
+    }
+
+    // the fields of a line starting with `keyword`
+    fn record(&mut self, keyword: &str) -> Result<Vec<String>, Error> {
+        let mut fields = self.next()?;
+
+        if fields.first().map(String::as_str) != Some(keyword) {
+            return Err(self.error(format!("expected {keyword}")));
+        }
+
+        fields.remove(0);
+        Ok(fields)
+    }
+
+    fn fields<const N: usize>(&mut self, keyword: &str) -> Result<[String; N], Error> {
+        let fields = self.record(keyword)?;
+        let count = fields.len();
+
+        fields.try_into().map_err(|_| {
+            self.error(format!(
+                "expected {N} values after {keyword}, found {count}"
+            ))
+        })
+    }
+
+    fn integer(&self, field: &str) -> Result<usize, Error> {
+        field
+            .parse()
+            .map_err(|_| self.error(format!("invalid integer {field}")))
+    }
+
+    fn float(&self, field: &str) -> Result<f64, Error> {
+        if field.len() != 16 {
+            return Err(self.error(format!("invalid number {field}")));
+        }
+
+        u64::from_str_radix(field, 16)
+            .map(f64::from_bits)
+            .map_err(|_| self.error(format!("invalid number {field}")))
+    }
+
+    // a fitness followed by the coordinates of a point
+    fn point<const D: usize>(&self, fields: &[String]) -> Result<(f64, [f64; D]), Error> {
+        if fields.len() != D + 1 {
+            return Err(self.error(format!(
+                "expected {} numbers, found {}",
+                D + 1,
+                fields.len()
+            )));
+        }
+
+        let fitness = self.float(&fields[0])?;
+
+        let mut x = [0.0; D];
+        for (xi, field) in x.iter_mut().zip(&fields[1..]) {
+            *xi = self.float(field)?;
+        }
+
+        Ok((fitness, x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::evolutionary_computation::{
+        genetic_algorithm, GeneticAlgorithm, Polynomial, Replacement, SimulatedBinary,
+        SteadyStatePolicy, Tournament,
+    };
+    use crate::metaheuristics::{run, Range};
+    use rand::rngs::{Xoshiro256Plus, Xoshiro256PlusPlus};
+
+    enum Sphere {}
+    impl Problem<3> for Sphere {
+        fn f(x: &[f64; 3]) -> f64 {
+            x.iter().map(|xi| xi * xi).sum()
+        }
+
+        const RANGES: [Range<f64>; 3] = [Range::new(-5.0, 5.0); 3];
+
+        const INEQUALITIES: &'static [fn(&[f64; 3]) -> f64] = &[];
+
+        const PENALTY_WEIGHT: f64 = 1.0;
+    }
+
+    #[test]
+    fn resuming_is_bit_identical() {
+        let parameters = GeneticAlgorithm {
+            population_size: 16,
+            selection: Tournament::new(2),
+            crossover: SimulatedBinary::new(15.0),
+            crossover_probability: 0.9,
+            mutation: Polynomial::new(0.3, 20.0),
+            replacement: Replacement::SteadyState {
+                offspring: 2,
+                policy: SteadyStatePolicy::ReplaceOldest,
+            },
+            workers: 1,
+        };
+        let termination = Termination::iterations(40);
+
+        let mut rng = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+        let uninterrupted = genetic_algorithm::<_, Sphere, _, _, _, _, 3>(
+            &mut rng,
+            termination,
+            &parameters,
+            &mut (),
+        );
+
+        let mut rng = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 3>(&mut rng);
+        for _ in 0..15 {
+            parameters.iterate::<_, Sphere, 3>(&mut rng, &mut population, &termination);
+        }
+
+        let mut file = Vec::new();
+        write(&mut file, &rng, &population).unwrap();
+        drop((rng, population));
+
+        let (mut rng, population) = read::<Xoshiro256PlusPlus, _, 3>(&file[..]).unwrap();
+        let resumed =
+            run::<_, Sphere, _, _, 3>(&mut rng, termination, &parameters, population, &mut ());
+
+        assert_eq!(resumed.fitness.to_bits(), uninterrupted.fitness.to_bits());
+        assert_eq!(
+            resumed.x.map(f64::to_bits),
+            uninterrupted.x.map(f64::to_bits)
+        );
+        assert_eq!(resumed.evaluations, uninterrupted.evaluations);
+    }
+
+    #[test]
+    fn errors_report_the_line() {
+        let text = "checkpoint 1\nrng xoshiro256+:0000000000000000-0000000000000000-0000000000000000-0000000000000000\n";
+
+        // an all-zero state must be rejected
+        crate::parse::assert_error_lines([(text, 2)], |text| {
+            read::<Xoshiro256Plus, _, 3>(text.as_bytes()).map(|_| ())
+        });
+    }
+}
@@ -0,0 +1,339 @@
+use rand::distributions::{Distribution, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{Solution, Tsp};
+use crate::metaheuristics::Termination;
+
+/// How the ants choose their next city and how the pheromone is updated.
+#[derive(Clone, Copy)]
+pub enum AntSystem {
+    /// Ant colony system of Dorigo and Gambardella. With probability
+    /// `exploitation` an ant goes to the city with the largest weight, and
+    /// otherwise it chooses in proportion to the weights. Every edge an ant
+    /// takes moves its pheromone towards the initial `1 / (n L)`, where `L`
+    /// is the length of the nearest neighbor tour, by `local_evaporation`.
+    /// After every iteration only the edges of the best tour so far evaporate
+    /// and receive `1 / L` of its length.
+    AntColonySystem {
+        exploitation: f64,
+        local_evaporation: f64,
+    },
+    /// MAX-MIN ant system of Stützle and Hoos. Ants choose in proportion to
+    /// the weights. After every iteration all the pheromone evaporates, and
+    /// the best tour of the iteration receives `1 / L` of its length, or the
+    /// best tour so far every `best_so_far_period` iterations. The pheromone
+    /// is kept between the `1 / (ρ L)` of the best tour so far and the
+    /// minimum that gives that tour a probability of `best_probability` of
+    /// being built again once the colony has converged.
+    MaxMin {
+        best_so_far_period: usize,
+        best_probability: f64,
+    },
+}
+
+/// Ant colony optimization for the travelling salesman problem. Ants build
+/// tours one city at a time from a random one, choosing among the unvisited
+/// cities of the candidate list of their current city, or among all the
+/// unvisited cities once none is left there, with weights of `τ^α η^β` for
+/// the pheromone `τ` of the edge and its heuristic value `η = 1 / d`.
+pub struct AntColony {
+    pub ants: usize,
+    /// Weight `α` of the pheromone, usually 1.
+    pub pheromone_weight: f64,
+    /// Weight `β` of the heuristic, usually between 2 and 5.
+    pub heuristic_weight: f64,
+    /// Evaporation rate `ρ` of the pheromone.
+    pub evaporation: f64,
+    /// Number of nearest cities in the candidate list of every city, usually
+    /// 15 to 20.
+    pub candidates: usize,
+    /// Whether every tour is improved with 2-opt moves.
+    pub two_opt: bool,
+    pub system: AntSystem,
+}
+
+/// Runs `parameters` on `tsp` until `termination` is met, counting one
+/// evaluation per tour built.
+///
+/// # Panics
+///
+/// If `tsp` has no cities.
+pub fn ant_colony<R>(
+    rng: &mut R,
+    tsp: &Tsp,
+    termination: Termination,
+    parameters: &AntColony,
+) -> Solution
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+{
+    let n = tsp.dimension();
+    assert!(n > 0, "the instance must have at least one city");
+
+    let candidates = tsp.nearest_neighbors(parameters.candidates);
+
+    let mut heuristic = vec![0.0; n * n];
+    for (k, eta) in heuristic.iter_mut().enumerate() {
+        let distance = tsp.distance(k / n, k % n).max(f64::EPSILON);
+        *eta = distance.recip().powf(parameters.heuristic_weight);
+    }
+
+    let nearest_neighbor_length = tsp.length(&tsp.nearest_neighbor_tour());
+    let initial = match parameters.system {
+        AntSystem::AntColonySystem { .. } => 1.0 / (n as f64 * nearest_neighbor_length),
+        AntSystem::MaxMin { .. } => 1.0 / (parameters.evaporation * nearest_neighbor_length),
+    };
+
+    let mut colony = Colony {
+        tsp,
+        parameters,
+        candidates,
+        heuristic,
+        pheromone: vec![initial; n * n],
+        initial,
+    };
+
+    let mut solution = Solution {
+        permutation: Vec::new(),
+        cost: f64::INFINITY,
+        iterations: 0,
+        evaluations: 0,
+    };
+
+    while !termination.is_met(solution.iterations, solution.evaluations, solution.cost) {
+        let mut iteration_best = (Vec::new(), f64::INFINITY);
+
+        for _ in 0..parameters.ants {
+            let mut tour = colony.construct(rng);
+            let length = if parameters.two_opt {
+                tsp.two_opt(&mut tour)
+            } else {
+                tsp.length(&tour)
+            };
+            solution.evaluations += 1;
+
+            if length < iteration_best.1 {
+                iteration_best = (tour, length);
+            }
+        }
+
+        if iteration_best.1 < solution.cost {
+            solution.permutation = iteration_best.0.clone();
+            solution.cost = iteration_best.1;
+        }
+
+        let rho = parameters.evaporation;
+        match parameters.system {
+            AntSystem::AntColonySystem { .. } => {
+                colony.deposit(&solution.permutation, 1.0 - rho, rho / solution.cost);
+            }
+            AntSystem::MaxMin {
+                best_so_far_period,
+                best_probability,
+            } => {
+                for tau in &mut colony.pheromone {
+                    *tau *= 1.0 - rho;
+                }
+
+                let (tour, length) =
+                    if (solution.iterations + 1).is_multiple_of(best_so_far_period.max(1)) {
+                        (&solution.permutation, solution.cost)
+                    } else {
+                        (&iteration_best.0, iteration_best.1)
+                    };
+                colony.deposit(tour, 1.0, 1.0 / length);
+
+                // an ant makes about n / 2 choices on average
+                let maximum = 1.0 / (rho * solution.cost);
+                let root = best_probability.powf(1.0 / n as f64);
+                let minimum = maximum * (1.0 - root) / ((n as f64 / 2.0 - 1.0).max(1.0) * root);
+
+                for tau in &mut colony.pheromone {
+                    *tau = tau.clamp(minimum.min(maximum), maximum);
+                }
+            }
+        }
+
+        solution.iterations += 1;
+    }
+
+    solution
+}
+
+struct Colony<'a> {
+    tsp: &'a Tsp,
+    parameters: &'a AntColony,
+    candidates: Vec<Vec<usize>>,
+    /// `η^β` of every edge.
+    heuristic: Vec<f64>,
+    pheromone: Vec<f64>,
+    initial: f64,
+}
+
+impl Colony<'_> {
+    fn weight(&self, from: usize, to: usize) -> f64 {
+        let k = from * self.tsp.dimension() + to;
+        self.pheromone[k].powf(self.parameters.pheromone_weight) * self.heuristic[k]
+    }
+
+    fn construct<R>(&mut self, rng: &mut R) -> Vec<usize>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let n = self.tsp.dimension();
+        let mut visited = vec![false; n];
+        let mut tour = Vec::with_capacity(n);
+
+        let start = rng.sample(&UniformClosedOpen::new(0 as f64, n as f64)) as usize;
+        tour.push(start);
+        visited[start] = true;
+
+        for _ in 1..n {
+            let current = tour[tour.len() - 1];
+            let next = self.choose(rng, current, &visited);
+
+            self.local_update(current, next);
+            tour.push(next);
+            visited[next] = true;
+        }
+        if n > 1 {
+            self.local_update(tour[n - 1], start);
+        }
+
+        tour
+    }
+
+    fn choose<R>(&self, rng: &mut R, current: usize, visited: &[bool]) -> usize
+    where
+        R: Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let options = self.candidates[current]
+            .iter()
+            .copied()
+            .filter(|&city| !visited[city])
+            .collect::<Vec<_>>();
+
+        let best = |cities: &mut dyn Iterator<Item = usize>| {
+            cities
+                .max_by(|&a, &b| self.weight(current, a).total_cmp(&self.weight(current, b)))
+                .unwrap()
+        };
+
+        if options.is_empty() {
+            return best(&mut (0..visited.len()).filter(|&city| !visited[city]));
+        }
+
+        if let AntSystem::AntColonySystem { exploitation, .. } = self.parameters.system {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < exploitation {
+                return best(&mut options.iter().copied());
+            }
+        }
+
+        let weights = options
+            .iter()
+            .map(|&city| self.weight(current, city))
+            .collect::<Vec<_>>();
+        let mut u = rng.sample::<f64, _>(&UniformClosedOpen01) * weights.iter().sum::<f64>();
+
+        for (&city, &weight) in options.iter().zip(&weights) {
+            if u < weight {
+                return city;
+            }
+            u -= weight;
+        }
+        options[options.len() - 1]
+    }
+
+    fn local_update(&mut self, from: usize, to: usize) {
+        if let AntSystem::AntColonySystem {
+            local_evaporation, ..
+        } = self.parameters.system
+        {
+            let update =
+                |tau: f64| (1.0 - local_evaporation) * tau + local_evaporation * self.initial;
+            self.set(
+                from,
+                to,
+                update(self.pheromone[from * self.tsp.dimension() + to]),
+            );
+        }
+    }
+
+    // multiplies the pheromone on the edges of `tour` by `keep` and adds
+    // `amount` to it
+    fn deposit(&mut self, tour: &[usize], keep: f64, amount: f64) {
+        let n = self.tsp.dimension();
+        for (&from, &to) in tour.iter().zip(tour.iter().cycle().skip(1)) {
+            self.set(from, to, keep * self.pheromone[from * n + to] + amount);
+        }
+    }
+
+    // the pheromone of a symmetric instance is kept symmetric
+    fn set(&mut self, from: usize, to: usize, tau: f64) {
+        let n = self.tsp.dimension();
+        self.pheromone[from * n + to] = tau;
+        if self.tsp.is_symmetric() {
+            self.pheromone[to * n + from] = tau;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn ant_colony_works() {
+        // cities on a circle, where the shortest tour goes around it
+        let n = 10;
+        let points = (0..n)
+            .map(|k| {
+                let angle = std::f64::consts::TAU * k as f64 / n as f64;
+                (angle.cos(), angle.sin())
+            })
+            .collect::<Vec<_>>();
+        let tsp = Tsp::new(
+            points
+                .iter()
+                .map(|a| {
+                    points
+                        .iter()
+                        .map(|b| f64::hypot(a.0 - b.0, a.1 - b.1))
+                        .collect()
+                })
+                .collect(),
+        );
+        let optimum = tsp.length(&(0..n).collect::<Vec<_>>());
+
+        for system in [
+            AntSystem::AntColonySystem {
+                exploitation: 0.9,
+                local_evaporation: 0.1,
+            },
+            AntSystem::MaxMin {
+                best_so_far_period: 5,
+                best_probability: 0.05,
+            },
+        ] {
+            let parameters = AntColony {
+                ants: 10,
+                pheromone_weight: 1.0,
+                heuristic_weight: 2.0,
+                evaporation: 0.1,
+                candidates: 5,
+                two_opt: false,
+                system,
+            };
+
+            let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+            let solution = ant_colony(&mut rng, &tsp, Termination::iterations(100), &parameters);
+
+            assert!((solution.cost - optimum).abs() < 1e-9);
+            assert_eq!(solution.cost, tsp.length(&solution.permutation));
+            assert_eq!(solution.evaluations, 100 * 10);
+        }
+    }
+}
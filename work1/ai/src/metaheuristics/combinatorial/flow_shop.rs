@@ -0,0 +1,77 @@
+use super::PermutationProblem;
+
+/// A permutation flow-shop instance, where every job goes through all the
+/// machines in the same order, the jobs are processed in the same order on
+/// every machine, and the cost is the makespan.
+#[derive(Clone, Debug)]
+pub struct FlowShop {
+    jobs: usize,
+    machines: usize,
+    processing_times: Vec<f64>,
+}
+
+impl FlowShop {
+    /// `processing_times[j][m]` is the time job `j` takes on machine `m`.
+    ///
+    /// # Panics
+    ///
+    /// If the jobs do not all have a time for the same number of machines.
+    pub fn new(processing_times: Vec<Vec<f64>>) -> Self {
+        let jobs = processing_times.len();
+        let machines = processing_times.first().map_or(0, Vec::len);
+        assert!(
+            processing_times.iter().all(|row| row.len() == machines),
+            "every job must have a time for every machine"
+        );
+
+        Self {
+            jobs,
+            machines,
+            processing_times: processing_times.concat(),
+        }
+    }
+
+    pub fn machines(&self) -> usize {
+        self.machines
+    }
+
+    pub fn processing_time(&self, job: usize, machine: usize) -> f64 {
+        self.processing_times[job * self.machines + machine]
+    }
+}
+
+impl PermutationProblem for FlowShop {
+    fn size(&self) -> usize {
+        self.jobs
+    }
+
+    fn cost(&self, permutation: &[usize]) -> f64 {
+        // completion time of the last job scheduled so far on every machine
+        let mut completion = vec![0.0; self.machines];
+
+        for &job in permutation {
+            let mut previous = 0.0;
+            for (machine, time) in completion.iter_mut().enumerate() {
+                *time = f64::max(*time, previous) + self.processing_time(job, machine);
+                previous = *time;
+            }
+        }
+
+        completion.last().copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_works() {
+        let flow_shop = FlowShop::new(vec![vec![3.0, 2.0], vec![1.0, 4.0], vec![2.0, 1.0]]);
+
+        // the second machine waits for job 1 until 4, and then is busy
+        assert_eq!(flow_shop.cost(&[0, 1, 2]), 10.0);
+        assert_eq!(flow_shop.cost(&[1, 0, 2]), 8.0);
+        assert_eq!(flow_shop.cost(&[]), 0.0);
+    }
+}
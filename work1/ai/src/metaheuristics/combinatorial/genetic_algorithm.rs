@@ -0,0 +1,447 @@
+use rand::distributions::{Distribution, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{PermutationProblem, Solution};
+use crate::metaheuristics::evolutionary_computation::Selection;
+use crate::metaheuristics::Termination;
+
+/// Recombines two parent permutations in place, turning them into two
+/// offspring.
+#[derive(Clone, Copy)]
+pub enum PermutationCrossover {
+    /// Order crossover (Davis), each offspring keeps a random segment of one
+    /// parent and takes the other elements in the order they have in the
+    /// other parent, starting after the segment.
+    Order,
+    /// Partially mapped crossover (Goldberg and Lingle), each offspring keeps
+    /// a random segment of one parent and the other positions of the other
+    /// parent, where the elements already in the segment are replaced through
+    /// the mapping between both segments.
+    PartiallyMapped,
+    /// Cycle crossover (Oliver, Smith and Holland), the positions are split
+    /// into the cycles of the mapping between the parents and the offspring
+    /// take every other cycle from each of them, so that every element keeps
+    /// its position in one of the parents.
+    Cycle,
+    /// Edge recombination (Whitley, Starkweather and Fuquay), each offspring
+    /// starts from the first element of a parent and goes to the unvisited
+    /// neighbour, in either parent, with the fewest unvisited neighbours
+    /// left, or to a random unvisited element once there is none. Meant for
+    /// problems where adjacency matters, like the TSP.
+    EdgeRecombination,
+}
+
+/// Perturbs a permutation in place.
+#[derive(Clone, Copy)]
+pub enum PermutationMutation {
+    /// Exchanges two random elements.
+    Swap,
+    /// Moves a random element to a random position.
+    Insertion,
+    /// Reverses a random segment, a 2-opt move for the TSP.
+    Inversion,
+    /// Shuffles a random segment.
+    Scramble,
+}
+
+pub struct PermutationGeneticAlgorithm<S> {
+    pub population_size: usize,
+    pub selection: S,
+    pub crossover: PermutationCrossover,
+    pub crossover_probability: f64,
+    pub mutation: PermutationMutation,
+    /// Probability of every offspring being mutated once.
+    pub mutation_probability: f64,
+    /// Number of best parents that take the places of the worst offspring.
+    pub elitism: usize,
+}
+
+/// Runs a generational genetic algorithm on `problem` from random
+/// permutations until `termination` is met. Consecutive parents are paired
+/// for the crossover.
+pub fn permutation_genetic_algorithm<R, P, S>(
+    rng: &mut R,
+    problem: &P,
+    termination: Termination,
+    parameters: &PermutationGeneticAlgorithm<S>,
+) -> Solution
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    P: PermutationProblem,
+    S: Selection,
+{
+    let n = problem.size();
+    let population_size = parameters.population_size;
+
+    let mut population = (0..population_size)
+        .map(|_| {
+            let mut permutation = (0..n).collect::<Vec<_>>();
+            rand::util::shuffle(rng, &mut permutation);
+            permutation
+        })
+        .collect::<Vec<_>>();
+    let mut fitnesses = population
+        .iter()
+        .map(|permutation| problem.cost(permutation))
+        .collect::<Vec<_>>();
+
+    let mut solution = Solution {
+        permutation: Vec::new(),
+        cost: f64::INFINITY,
+        iterations: 0,
+        evaluations: population_size,
+    };
+    update_best(&mut solution, &population, &fitnesses);
+
+    let mut parents = vec![0; population_size];
+
+    while !termination.is_met(solution.iterations, solution.evaluations, solution.cost) {
+        parameters.selection.select(rng, &fitnesses, &mut parents);
+        let mut offspring = parents
+            .iter()
+            .map(|&j| population[j].clone())
+            .collect::<Vec<_>>();
+
+        for pair in offspring.chunks_exact_mut(2) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < parameters.crossover_probability {
+                let (a, b) = pair.split_at_mut(1);
+                parameters.crossover.crossover(rng, &mut a[0], &mut b[0]);
+            }
+        }
+
+        for individual in &mut offspring {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < parameters.mutation_probability {
+                parameters.mutation.mutate(rng, individual);
+            }
+        }
+
+        let mut offspring_fitnesses = offspring
+            .iter()
+            .map(|permutation| problem.cost(permutation))
+            .collect::<Vec<_>>();
+        solution.evaluations += population_size;
+
+        let elitism = usize::min(parameters.elitism, population_size);
+        let best = sorted_indexes(&fitnesses);
+        let worst = sorted_indexes(&offspring_fitnesses);
+
+        for (&i, &j) in best[..elitism].iter().zip(worst.iter().rev()) {
+            offspring[j] = population[i].clone();
+            offspring_fitnesses[j] = fitnesses[i];
+        }
+
+        population = offspring;
+        fitnesses = offspring_fitnesses;
+        update_best(&mut solution, &population, &fitnesses);
+
+        solution.iterations += 1;
+    }
+
+    solution
+}
+
+impl PermutationCrossover {
+    pub fn crossover<R>(&self, rng: &mut R, a: &mut [usize], b: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
+    {
+        let n = a.len();
+        if n < 2 {
+            return;
+        }
+
+        let (c1, c2) = match self {
+            Self::Order => {
+                let (start, end) = sorted(distinct_positions(rng, n));
+                (order(a, b, start, end), order(b, a, start, end))
+            }
+            Self::PartiallyMapped => {
+                let (start, end) = sorted(distinct_positions(rng, n));
+                (
+                    partially_mapped(a, b, start, end),
+                    partially_mapped(b, a, start, end),
+                )
+            }
+            Self::Cycle => {
+                cycle(a, b);
+                return;
+            }
+            Self::EdgeRecombination => (
+                edge_recombination(rng, a, b, a[0]),
+                edge_recombination(rng, a, b, b[0]),
+            ),
+        };
+
+        a.copy_from_slice(&c1);
+        b.copy_from_slice(&c2);
+    }
+}
+
+impl PermutationMutation {
+    pub fn mutate<R>(&self, rng: &mut R, permutation: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
+    {
+        if permutation.len() < 2 {
+            return;
+        }
+
+        let (i, j) = distinct_positions(rng, permutation.len());
+        match self {
+            Self::Swap => permutation.swap(i, j),
+            Self::Insertion => {
+                if i < j {
+                    permutation[i..=j].rotate_left(1);
+                } else {
+                    permutation[j..=i].rotate_right(1);
+                }
+            }
+            Self::Inversion => {
+                let (start, end) = sorted((i, j));
+                permutation[start..=end].reverse();
+            }
+            Self::Scramble => {
+                let (start, end) = sorted((i, j));
+                rand::util::shuffle(rng, &mut permutation[start..=end]);
+            }
+        }
+    }
+}
+
+fn update_best(solution: &mut Solution, population: &[Vec<usize>], fitnesses: &[f64]) {
+    for (permutation, &fitness) in population.iter().zip(fitnesses) {
+        if fitness < solution.cost {
+            solution.permutation.clone_from(permutation);
+            solution.cost = fitness;
+        }
+    }
+}
+
+fn sorted_indexes(fitnesses: &[f64]) -> Vec<usize> {
+    let mut order = (0..fitnesses.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| fitnesses[a].total_cmp(&fitnesses[b]));
+    order
+}
+
+// two different positions of a sequence of length n ≥ 2
+fn distinct_positions<R>(rng: &mut R, n: usize) -> (usize, usize)
+where
+    R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
+{
+    let i = rng.sample(&UniformClosedOpen::new(0, n));
+    let j = rng.sample(&UniformClosedOpen::new(0, n - 1));
+    (i, if j >= i { j + 1 } else { j })
+}
+
+fn sorted((i, j): (usize, usize)) -> (usize, usize) {
+    (usize::min(i, j), usize::max(i, j))
+}
+
+// the offspring keeping `keep[start..=end]`
+fn order(keep: &[usize], other: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let n = keep.len();
+
+    let mut kept = vec![false; n];
+    for &x in &keep[start..=end] {
+        kept[x] = true;
+    }
+
+    let mut child = keep.to_vec();
+    let mut rest = (1..=n).map(|k| other[(end + k) % n]).filter(|&x| !kept[x]);
+    for k in 1..=n - (end - start + 1) {
+        child[(end + k) % n] = rest.next().unwrap();
+    }
+
+    child
+}
+
+// the offspring keeping `keep[start..=end]`
+fn partially_mapped(keep: &[usize], other: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let n = keep.len();
+
+    let mut position = vec![0; n];
+    for (k, &x) in keep.iter().enumerate() {
+        position[x] = k;
+    }
+
+    let mut child = other.to_vec();
+    child[start..=end].copy_from_slice(&keep[start..=end]);
+
+    for k in (0..start).chain(end + 1..n) {
+        let mut x = other[k];
+        while (start..=end).contains(&position[x]) {
+            x = other[position[x]];
+        }
+        child[k] = x;
+    }
+
+    child
+}
+
+fn cycle(a: &mut [usize], b: &mut [usize]) {
+    let n = a.len();
+
+    let mut position = vec![0; n];
+    for (k, &x) in a.iter().enumerate() {
+        position[x] = k;
+    }
+
+    let mut visited = vec![false; n];
+    let mut exchange = false;
+
+    for start in 0..n {
+        if visited[start] {
+            continue;
+        }
+
+        let mut k = start;
+        while !visited[k] {
+            visited[k] = true;
+            let next = position[b[k]];
+            if exchange {
+                std::mem::swap(&mut a[k], &mut b[k]);
+            }
+            k = next;
+        }
+
+        exchange = !exchange;
+    }
+}
+
+fn edge_recombination<R>(rng: &mut R, a: &[usize], b: &[usize], first: usize) -> Vec<usize>
+where
+    R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
+{
+    let n = a.len();
+
+    // the union of the neighbours in both parents, at most four
+    let mut neighbors = vec![Vec::with_capacity(4); n];
+    for parent in [a, b] {
+        for k in 0..n {
+            for y in [parent[(k + n - 1) % n], parent[(k + 1) % n]] {
+                if !neighbors[parent[k]].contains(&y) {
+                    neighbors[parent[k]].push(y);
+                }
+            }
+        }
+    }
+
+    let mut visited = vec![false; n];
+    let mut child = Vec::with_capacity(n);
+    let mut current = first;
+
+    loop {
+        child.push(current);
+        visited[current] = true;
+        if child.len() == n {
+            return child;
+        }
+
+        let unvisited = |x: &usize| !visited[*x];
+        let remaining = |x: usize| neighbors[x].iter().filter(|x| unvisited(x)).count();
+
+        let mut options = neighbors[current]
+            .iter()
+            .copied()
+            .filter(unvisited)
+            .collect::<Vec<_>>();
+
+        if options.is_empty() {
+            options = (0..n).filter(unvisited).collect();
+        } else {
+            let fewest = options.iter().map(|&x| remaining(x)).min().unwrap();
+            options.retain(|&x| remaining(x) == fewest);
+        }
+
+        current = options[rng.sample(&UniformClosedOpen::new(0, options.len()))];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn is_permutation(permutation: &[usize]) -> bool {
+        let mut sorted = permutation.to_vec();
+        sorted.sort_unstable();
+        sorted.iter().enumerate().all(|(i, &x)| i == x)
+    }
+
+    #[test]
+    fn operators_keep_permutations() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+
+        let crossovers = [
+            PermutationCrossover::Order,
+            PermutationCrossover::PartiallyMapped,
+            PermutationCrossover::Cycle,
+            PermutationCrossover::EdgeRecombination,
+        ];
+        let mutations = [
+            PermutationMutation::Swap,
+            PermutationMutation::Insertion,
+            PermutationMutation::Inversion,
+            PermutationMutation::Scramble,
+        ];
+
+        for n in 1..12 {
+            for _ in 0..200 {
+                let mut a = (0..n).collect::<Vec<_>>();
+                let mut b = (0..n).collect::<Vec<_>>();
+                rand::util::shuffle(&mut rng, &mut a);
+                rand::util::shuffle(&mut rng, &mut b);
+
+                for crossover in crossovers {
+                    let (mut x, mut y) = (a.clone(), b.clone());
+                    crossover.crossover(&mut rng, &mut x, &mut y);
+                    assert!(is_permutation(&x) && is_permutation(&y), "{a:?} {b:?}");
+
+                    // every element keeps its position in one of the parents
+                    if let PermutationCrossover::Cycle = crossover {
+                        for k in 0..n {
+                            assert!(
+                                (x[k], y[k]) == (a[k], b[k]) || (x[k], y[k]) == (b[k], a[k]),
+                                "{a:?} {b:?}"
+                            );
+                        }
+                    }
+                }
+
+                for mutation in mutations {
+                    let mut x = a.clone();
+                    mutation.mutate(&mut rng, &mut x);
+                    assert!(is_permutation(&x), "{a:?}");
+                    if let PermutationMutation::Swap = mutation {
+                        let moved = (0..n).filter(|&k| x[k] != a[k]).count();
+                        assert_eq!(moved, if n < 2 { 0 } else { 2 });
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn segment_crossovers_work() {
+        let a = [0, 1, 2, 3, 4, 5, 6, 7];
+        let b = [3, 7, 5, 1, 6, 0, 2, 4];
+
+        // the segment of `a` from 2 to 4, then 0, 7, 5, 1, 6 from the rest
+        // of `b` in its order after the segment
+        assert_eq!(order(&a, &b, 2, 4), [1, 6, 2, 3, 4, 0, 7, 5]);
+        // 3, 2 and 4 of `b` are mapped to 1, 5 and 6 through the segments
+        assert_eq!(partially_mapped(&a, &b, 2, 4), [1, 7, 2, 3, 4, 0, 5, 6]);
+
+        let (mut x, mut y) = (a, b);
+        cycle(&mut x, &mut y);
+        // the cycle through position 0 is 0, 3, 1, 7, 4, 6, 2, 5, the whole
+        // permutation, so nothing is exchanged
+        assert_eq!((x, y), (a, b));
+
+        let (mut x, mut y) = ([0, 1, 2, 3], [1, 0, 3, 2]);
+        cycle(&mut x, &mut y);
+        assert_eq!((x, y), ([0, 1, 3, 2], [1, 0, 2, 3]));
+    }
+}
@@ -0,0 +1,32 @@
+mod ant_colony;
+mod flow_shop;
+mod genetic_algorithm;
+mod qap;
+mod tsp;
+pub mod tsplib;
+
+pub use ant_colony::{ant_colony, AntColony, AntSystem};
+pub use flow_shop::FlowShop;
+pub use genetic_algorithm::{
+    permutation_genetic_algorithm, PermutationCrossover, PermutationGeneticAlgorithm,
+    PermutationMutation,
+};
+pub use qap::Qap;
+pub use tsp::Tsp;
+
+/// A problem whose solutions are the orderings of `0..size()`, of which the
+/// cost is minimized.
+pub trait PermutationProblem {
+    fn size(&self) -> usize;
+
+    fn cost(&self, permutation: &[usize]) -> f64;
+}
+
+/// Best permutation found by a run, along with the cost of finding it.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub permutation: Vec<usize>,
+    pub cost: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+}
@@ -0,0 +1,86 @@
+use super::PermutationProblem;
+
+/// A quadratic assignment instance, where `n` facilities are placed at `n`
+/// locations, facility `i` at location `p[i]`, and the cost is the sum over
+/// all pairs of facilities of their flow times the distance between their
+/// locations.
+#[derive(Clone, Debug)]
+pub struct Qap {
+    size: usize,
+    flows: Vec<f64>,
+    distances: Vec<f64>,
+}
+
+impl Qap {
+    /// # Panics
+    ///
+    /// If `flows` and `distances` are not square matrices of the same size.
+    pub fn new(flows: Vec<Vec<f64>>, distances: Vec<Vec<f64>>) -> Self {
+        let size = flows.len();
+        assert!(
+            flows.iter().chain(&distances).all(|row| row.len() == size) && distances.len() == size,
+            "the flow and distance matrices must be square and of the same size"
+        );
+
+        Self {
+            size,
+            flows: flows.concat(),
+            distances: distances.concat(),
+        }
+    }
+
+    pub fn flow(&self, from: usize, to: usize) -> f64 {
+        self.flows[from * self.size + to]
+    }
+
+    pub fn distance(&self, from: usize, to: usize) -> f64 {
+        self.distances[from * self.size + to]
+    }
+}
+
+impl PermutationProblem for Qap {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn cost(&self, permutation: &[usize]) -> f64 {
+        let mut cost = 0.0;
+        for (i, &a) in permutation.iter().enumerate() {
+            for (j, &b) in permutation.iter().enumerate() {
+                cost += self.flow(i, j) * self.distance(a, b);
+            }
+        }
+        cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_works() {
+        let qap = Qap::new(
+            vec![
+                vec![0.0, 1.0, 2.0],
+                vec![1.0, 0.0, 3.0],
+                vec![2.0, 3.0, 0.0],
+            ],
+            vec![
+                vec![0.0, 5.0, 1.0],
+                vec![5.0, 0.0, 4.0],
+                vec![1.0, 4.0, 0.0],
+            ],
+        );
+
+        // every pair is counted both ways
+        assert_eq!(
+            qap.cost(&[0, 1, 2]),
+            2.0 * (1.0 * 5.0 + 2.0 * 1.0 + 3.0 * 4.0)
+        );
+        assert_eq!(
+            qap.cost(&[1, 0, 2]),
+            2.0 * (1.0 * 5.0 + 2.0 * 4.0 + 3.0 * 1.0)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use super::PermutationProblem;
+
+/// A travelling salesman instance given by its distance matrix, which may be
+/// asymmetric, in which case `distance(i, j)` is the cost of going from `i`
+/// to `j`.
+#[derive(Clone, Debug)]
+pub struct Tsp {
+    dimension: usize,
+    distances: Vec<f64>,
+    symmetric: bool,
+}
+
+impl Tsp {
+    /// # Panics
+    ///
+    /// If `distances` is not square.
+    pub fn new(distances: Vec<Vec<f64>>) -> Self {
+        let dimension = distances.len();
+        assert!(
+            distances.iter().all(|row| row.len() == dimension),
+            "the distance matrix must be square"
+        );
+
+        let distances = distances.concat();
+        let symmetric = (0..dimension)
+            .all(|i| (0..i).all(|j| distances[i * dimension + j] == distances[j * dimension + i]));
+
+        Self {
+            dimension,
+            distances,
+            symmetric,
+        }
+    }
+
+    /// Number of cities.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn distance(&self, from: usize, to: usize) -> f64 {
+        self.distances[from * self.dimension + to]
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Length of the closed tour visiting the cities in the order of `tour`.
+    pub fn length(&self, tour: &[usize]) -> f64 {
+        tour.iter()
+            .zip(tour.iter().cycle().skip(1))
+            .map(|(&from, &to)| self.distance(from, to))
+            .sum()
+    }
+
+    /// The `k` cities closest to every city, nearest first.
+    pub fn nearest_neighbors(&self, k: usize) -> Vec<Vec<usize>> {
+        (0..self.dimension)
+            .map(|i| {
+                let mut neighbors = (0..self.dimension).filter(|&j| j != i).collect::<Vec<_>>();
+                neighbors.sort_by(|&a, &b| self.distance(i, a).total_cmp(&self.distance(i, b)));
+                neighbors.truncate(k);
+                neighbors
+            })
+            .collect()
+    }
+
+    /// The tour that always goes to the nearest unvisited city, from city 0.
+    pub fn nearest_neighbor_tour(&self) -> Vec<usize> {
+        let mut tour = Vec::with_capacity(self.dimension);
+        let mut visited = vec![false; self.dimension];
+
+        let mut city = 0;
+        for _ in 0..self.dimension {
+            tour.push(city);
+            visited[city] = true;
+
+            city = (0..self.dimension)
+                .filter(|&j| !visited[j])
+                .min_by(|&a, &b| self.distance(city, a).total_cmp(&self.distance(city, b)))
+                .unwrap_or(0);
+        }
+
+        tour
+    }
+
+    /// Improves `tour` with 2-opt moves, which reverse a part of it, until
+    /// none shortens it, and returns its length. Reversing a part of an
+    /// asymmetric tour also changes the cost of that part, which is taken
+    /// into account.
+    pub fn two_opt(&self, tour: &mut [usize]) -> f64 {
+        let n = tour.len();
+        if n < 4 {
+            return self.length(tour);
+        }
+
+        // costs of the first k edges of the tour, forwards and backwards
+        let mut forward = vec![0.0; n];
+        let mut backward = vec![0.0; n];
+        self.sum_edges(tour, &mut forward, &mut backward);
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+
+            for i in 0..n - 2 {
+                let last = if i == 0 { n - 1 } else { n };
+
+                for j in i + 2..last {
+                    if self.delta(tour, &forward, &backward, i, j) < -1e-10 {
+                        tour[i + 1..=j].reverse();
+                        self.sum_edges(tour, &mut forward, &mut backward);
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        self.length(tour)
+    }
+
+    // change in length from reversing `tour[i + 1..=j]`, where the edges
+    // (a, b) and (c, d) become (a, c) and (b, d)
+    fn delta(&self, tour: &[usize], forward: &[f64], backward: &[f64], i: usize, j: usize) -> f64 {
+        let n = tour.len();
+        let (a, b, c, d) = (tour[i], tour[i + 1], tour[j], tour[(j + 1) % n]);
+
+        let mut delta =
+            self.distance(a, c) + self.distance(b, d) - self.distance(a, b) - self.distance(c, d);
+        if !self.symmetric {
+            delta += (backward[j] - backward[i + 1]) - (forward[j] - forward[i + 1]);
+        }
+        delta
+    }
+
+    // only needed when reversing a part of a tour changes its cost
+    fn sum_edges(&self, tour: &[usize], forward: &mut [f64], backward: &mut [f64]) {
+        if self.symmetric {
+            return;
+        }
+
+        for k in 1..tour.len() {
+            forward[k] = forward[k - 1] + self.distance(tour[k - 1], tour[k]);
+            backward[k] = backward[k - 1] + self.distance(tour[k], tour[k - 1]);
+        }
+    }
+}
+
+impl PermutationProblem for Tsp {
+    fn size(&self) -> usize {
+        self.dimension
+    }
+
+    fn cost(&self, permutation: &[usize]) -> f64 {
+        self.length(permutation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::UniformClosedOpen;
+    use rand::rngs::{Rng, Xoshiro256Plus};
+
+    fn random_tsp(rng: &mut Xoshiro256Plus, n: usize) -> Tsp {
+        let mut distances = vec![vec![0.0; n]; n];
+        for (i, row) in distances.iter_mut().enumerate() {
+            for (j, distance) in row.iter_mut().enumerate() {
+                if i != j {
+                    *distance = rng.sample(&UniformClosedOpen::new(1.0, 100.0));
+                }
+            }
+        }
+        Tsp::new(distances)
+    }
+
+    #[test]
+    fn two_opt_deltas_work() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+
+        for n in 4..10 {
+            let tsp = random_tsp(&mut rng, n);
+            assert!(!tsp.is_symmetric());
+
+            let mut tour = (0..n).collect::<Vec<_>>();
+            rand::util::shuffle(&mut rng, &mut tour);
+
+            let mut forward = vec![0.0; n];
+            let mut backward = vec![0.0; n];
+            tsp.sum_edges(&tour, &mut forward, &mut backward);
+
+            let length = tsp.length(&tour);
+            for i in 0..n - 2 {
+                for j in i + 2..if i == 0 { n - 1 } else { n } {
+                    let mut moved = tour.clone();
+                    moved[i + 1..=j].reverse();
+
+                    let delta = tsp.delta(&tour, &forward, &backward, i, j);
+                    assert!((length + delta - tsp.length(&moved)).abs() < 1e-9);
+                }
+            }
+
+            // no move improves the tour anymore
+            let length = tsp.two_opt(&mut tour);
+            assert!((length - tsp.length(&tour)).abs() < 1e-9);
+
+            tsp.sum_edges(&tour, &mut forward, &mut backward);
+            for i in 0..n - 2 {
+                for j in i + 2..if i == 0 { n - 1 } else { n } {
+                    assert!(tsp.delta(&tour, &forward, &backward, i, j) >= -1e-10);
+                }
+            }
+        }
+    }
+}
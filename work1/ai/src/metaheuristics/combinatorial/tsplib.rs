@@ -0,0 +1,491 @@
+//! Reader for symmetric and asymmetric travelling salesman instances in the
+//! TSPLIB format, with `EUC_2D`, `CEIL_2D`, `ATT`, `GEO` or `EXPLICIT` edge
+//! weights, the latter as a `FULL_MATRIX`, `UPPER_ROW` or `LOWER_DIAG_ROW`,
+//! and for the tours of `.opt.tour` files.
+
+use std::fs;
+use std::path::Path;
+
+use super::Tsp;
+
+pub use crate::parse::Error;
+
+#[derive(Clone, Debug)]
+pub struct Instance {
+    pub name: String,
+    /// The comments of the file, one per line.
+    pub comment: String,
+    pub tsp: Tsp,
+}
+
+pub fn read(path: impl AsRef<Path>) -> Result<Instance, Error> {
+    parse(&fs::read_to_string(path)?)
+}
+
+pub fn parse(text: &str) -> Result<Instance, Error> {
+    let lines = Lines::new(text);
+
+    let mut name = String::new();
+    let mut comments = Vec::new();
+    let mut dimension = None;
+    let mut weight_type = None;
+    let mut weight_format = None;
+    let mut coordinates = None;
+    let mut weights = None;
+
+    let mut i = 0;
+    while let Some((line, key, value)) = lines.keyword(&mut i)? {
+        match key {
+            "NAME" => name = value.to_string(),
+            "COMMENT" => comments.push(value),
+            "TYPE" => {
+                if !matches!(value, "TSP" | "ATSP") {
+                    return Err(lines.error(line, format!("unsupported type {value}")));
+                }
+            }
+            "DIMENSION" => match value.parse::<usize>() {
+                Ok(n) if n > 0 => dimension = Some(n),
+                _ => return Err(lines.error(line, format!("invalid dimension `{value}`"))),
+            },
+            "EDGE_WEIGHT_TYPE" => {
+                if !matches!(value, "EUC_2D" | "CEIL_2D" | "ATT" | "GEO" | "EXPLICIT") {
+                    return Err(lines.error(line, format!("unsupported edge weight type {value}")));
+                }
+                weight_type = Some(value);
+            }
+            "EDGE_WEIGHT_FORMAT" => {
+                if !matches!(
+                    value,
+                    "FUNCTION" | "FULL_MATRIX" | "UPPER_ROW" | "LOWER_DIAG_ROW"
+                ) {
+                    return Err(
+                        lines.error(line, format!("unsupported edge weight format {value}"))
+                    );
+                }
+                weight_format = Some(value);
+            }
+            "NODE_COORD_TYPE" | "DISPLAY_DATA_TYPE" => {}
+            "NODE_COORD_SECTION" => {
+                let n = dimension.ok_or_else(|| lines.error(line, "DIMENSION must come first"))?;
+                coordinates = Some(lines.coordinates(&mut i, n, line)?);
+            }
+            "EDGE_WEIGHT_SECTION" => {
+                let n = dimension.ok_or_else(|| lines.error(line, "DIMENSION must come first"))?;
+                let format = match weight_format {
+                    Some("FUNCTION") => {
+                        return Err(lines.error(line, "edge weights given for FUNCTION"))
+                    }
+                    Some(format) => format,
+                    None => return Err(lines.error(line, "EDGE_WEIGHT_FORMAT must come first")),
+                };
+                weights = Some(lines.weights(&mut i, n, format, line)?);
+            }
+            "DISPLAY_DATA_SECTION" => {
+                lines.numbers(&mut i);
+            }
+            "EOF" => break,
+            _ => return Err(lines.error(line, format!("unknown keyword {key}"))),
+        }
+    }
+
+    let end = lines.lines.len();
+    dimension.ok_or_else(|| lines.error(end, "missing DIMENSION"))?;
+    let weight_type = weight_type.ok_or_else(|| lines.error(end, "missing EDGE_WEIGHT_TYPE"))?;
+
+    let distances = if weight_type == "EXPLICIT" {
+        weights.ok_or_else(|| lines.error(end, "missing EDGE_WEIGHT_SECTION"))?
+    } else {
+        let coordinates =
+            coordinates.ok_or_else(|| lines.error(end, "missing NODE_COORD_SECTION"))?;
+        let distance = match weight_type {
+            "EUC_2D" => euclidean,
+            "CEIL_2D" => ceiling,
+            "ATT" => pseudo_euclidean,
+            _ => geographical,
+        };
+
+        coordinates
+            .iter()
+            .map(|&a| coordinates.iter().map(|&b| distance(a, b)).collect())
+            .collect()
+    };
+
+    Ok(Instance {
+        name,
+        comment: comments.join("\n"),
+        tsp: Tsp::new(distances),
+    })
+}
+
+/// Reads the first tour of a `.opt.tour` file, as indexes from 0.
+pub fn read_tour(path: impl AsRef<Path>) -> Result<Vec<usize>, Error> {
+    parse_tour(&fs::read_to_string(path)?)
+}
+
+pub fn parse_tour(text: &str) -> Result<Vec<usize>, Error> {
+    let lines = Lines::new(text);
+    let mut dimension = None;
+
+    let mut i = 0;
+    while let Some((line, key, value)) = lines.keyword(&mut i)? {
+        match key {
+            "NAME" | "COMMENT" => {}
+            "TYPE" => {
+                if value != "TOUR" {
+                    return Err(lines.error(line, format!("expected a TOUR, found {value}")));
+                }
+            }
+            "DIMENSION" => match value.parse::<usize>() {
+                Ok(n) if n > 0 => dimension = Some(n),
+                _ => return Err(lines.error(line, format!("invalid dimension `{value}`"))),
+            },
+            "TOUR_SECTION" => {
+                let numbers = lines.numbers(&mut i);
+                let end = numbers
+                    .iter()
+                    .position(|&(_, token)| token == "-1")
+                    .unwrap_or(numbers.len());
+
+                let n = dimension.unwrap_or(end);
+                let mut seen = vec![false; n];
+                let mut tour = Vec::with_capacity(n);
+
+                for &(line, token) in &numbers[..end] {
+                    let city = match token.parse::<usize>() {
+                        Ok(city) if (1..=n).contains(&city) => city - 1,
+                        _ => return Err(lines.error(line, format!("invalid city `{token}`"))),
+                    };
+                    if seen[city] {
+                        return Err(lines.error(line, format!("city {token} is visited twice")));
+                    }
+                    seen[city] = true;
+                    tour.push(city);
+                }
+
+                if tour.len() != n {
+                    let line = numbers.get(end).map_or(i, |&(line, _)| line);
+                    return Err(lines.error(
+                        line,
+                        format!("the tour visits {} of {n} cities", tour.len()),
+                    ));
+                }
+                return Ok(tour);
+            }
+            "EOF" => break,
+            _ => return Err(lines.error(line, format!("unknown keyword {key}"))),
+        }
+    }
+
+    Err(lines.error(lines.lines.len(), "missing TOUR_SECTION"))
+}
+
+fn euclidean(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (f64::hypot(a.0 - b.0, a.1 - b.1) + 0.5).floor()
+}
+
+fn ceiling(a: (f64, f64), b: (f64, f64)) -> f64 {
+    f64::hypot(a.0 - b.0, a.1 - b.1).ceil()
+}
+
+// the distance of the att48 and att532 instances, rounded up
+fn pseudo_euclidean(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let r = f64::sqrt(((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)) / 10.0);
+    let t = (r + 0.5).floor();
+    if t < r {
+        t + 1.0
+    } else {
+        t
+    }
+}
+
+// great circle distance in kilometers between (latitude, longitude) pairs
+// written as degrees.minutes
+fn geographical(a: (f64, f64), b: (f64, f64)) -> f64 {
+    // the known optima were computed with this truncated value
+    #[allow(clippy::approx_constant)]
+    const PI: f64 = 3.141592;
+    const RADIUS: f64 = 6378.388;
+
+    let radians = |x: f64| {
+        let degrees = x.trunc();
+        PI * (degrees + 5.0 * (x - degrees) / 3.0) / 180.0
+    };
+    let (latitude_a, longitude_a) = (radians(a.0), radians(a.1));
+    let (latitude_b, longitude_b) = (radians(b.0), radians(b.1));
+
+    let q1 = f64::cos(longitude_a - longitude_b);
+    let q2 = f64::cos(latitude_a - latitude_b);
+    let q3 = f64::cos(latitude_a + latitude_b);
+
+    (RADIUS * f64::acos(0.5 * ((1.0 + q1) * q2 - (1.0 - q1) * q3)) + 1.0).trunc()
+}
+
+// the trimmed lines of a file, numbered from 1
+struct Lines<'a> {
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            lines: text
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.trim()))
+                .collect(),
+        }
+    }
+
+    fn error(&self, line: usize, message: impl Into<String>) -> Error {
+        Error::at(line, message)
+    }
+
+    // the next `KEY : value` or `KEY` line from `i`, skipping blank lines
+    fn keyword(&self, i: &mut usize) -> Result<Option<(usize, &'a str, &'a str)>, Error> {
+        while let Some(&(line, content)) = self.lines.get(*i) {
+            *i += 1;
+            if content.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match content.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (content, ""),
+            };
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+                return Err(self.error(line, format!("expected a keyword, found `{content}`")));
+            }
+
+            return Ok(Some((line, key, value)));
+        }
+        Ok(None)
+    }
+
+    // the tokens of the data lines from `i`, up to the next keyword
+    fn numbers(&self, i: &mut usize) -> Vec<(usize, &'a str)> {
+        let mut numbers = Vec::new();
+        while let Some(&(line, content)) = self.lines.get(*i) {
+            if content.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                break;
+            }
+            numbers.extend(content.split_whitespace().map(|token| (line, token)));
+            *i += 1;
+        }
+        numbers
+    }
+
+    fn coordinates(
+        &self,
+        i: &mut usize,
+        n: usize,
+        section: usize,
+    ) -> Result<Vec<(f64, f64)>, Error> {
+        let mut coordinates = vec![None; n];
+
+        while let Some(&(line, content)) = self.lines.get(*i) {
+            if content.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                break;
+            }
+            *i += 1;
+            if content.is_empty() {
+                continue;
+            }
+
+            let tokens = content.split_whitespace().collect::<Vec<_>>();
+            let [node, x, y] = tokens[..] else {
+                return Err(self.error(line, "expected a node and its two coordinates"));
+            };
+
+            let node = match node.parse::<usize>() {
+                Ok(node) if (1..=n).contains(&node) => node - 1,
+                _ => return Err(self.error(line, format!("invalid node `{node}`"))),
+            };
+            if coordinates[node].is_some() {
+                return Err(self.error(line, format!("node {} is given twice", node + 1)));
+            }
+            coordinates[node] = Some((self.number(line, x)?, self.number(line, y)?));
+        }
+
+        coordinates
+            .into_iter()
+            .enumerate()
+            .map(|(node, point)| {
+                point.ok_or_else(|| {
+                    self.error(section, format!("node {} has no coordinates", node + 1))
+                })
+            })
+            .collect()
+    }
+
+    fn weights(
+        &self,
+        i: &mut usize,
+        n: usize,
+        format: &str,
+        section: usize,
+    ) -> Result<Vec<Vec<f64>>, Error> {
+        // the entries the weights are given for, in order
+        let entries = (0..n)
+            .flat_map(|row| {
+                let columns = match format {
+                    "FULL_MATRIX" => 0..n,
+                    "UPPER_ROW" => row + 1..n,
+                    _ => 0..row + 1,
+                };
+                columns.map(move |column| (row, column))
+            })
+            .collect::<Vec<_>>();
+
+        let numbers = self.numbers(i);
+        if numbers.len() < entries.len() {
+            let line = numbers.last().map_or(section, |&(line, _)| line);
+            return Err(self.error(
+                line,
+                format!(
+                    "expected {} edge weights, found {}",
+                    entries.len(),
+                    numbers.len()
+                ),
+            ));
+        }
+        if let Some(&(line, token)) = numbers.get(entries.len()) {
+            return Err(self.error(line, format!("unexpected edge weight `{token}`")));
+        }
+
+        let mut distances = vec![vec![0.0; n]; n];
+        for (&(row, column), &(line, token)) in entries.iter().zip(&numbers) {
+            let weight = self.number(line, token)?;
+            distances[row][column] = weight;
+            if format != "FULL_MATRIX" {
+                distances[column][row] = weight;
+            }
+        }
+        Ok(distances)
+    }
+
+    fn number(&self, line: usize, token: &str) -> Result<f64, Error> {
+        token
+            .parse()
+            .map_err(|_| self.error(line, format!("invalid number `{token}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BURMA14: &str = "NAME: burma14
+TYPE: TSP
+COMMENT: 14-Staedte in Burma (Zaw Win)
+DIMENSION: 14
+EDGE_WEIGHT_TYPE: GEO
+EDGE_WEIGHT_FORMAT: FUNCTION
+DISPLAY_DATA_TYPE: COORD_DISPLAY
+NODE_COORD_SECTION
+   1  16.47       96.10
+   2  16.47       94.44
+   3  20.09       92.54
+   4  22.39       93.37
+   5  25.23       97.24
+   6  22.00       96.05
+   7  20.47       97.02
+   8  17.20       96.29
+   9  16.30       97.38
+  10  14.05       98.12
+  11  16.53       97.38
+  12  21.52       95.59
+  13  19.41       97.13
+  14  20.09       94.55
+EOF
+";
+
+    #[test]
+    fn parse_works() {
+        let instance = parse(BURMA14).unwrap();
+        assert_eq!(instance.name, "burma14");
+        assert_eq!(instance.tsp.dimension(), 14);
+        assert!(instance.tsp.is_symmetric());
+        assert_eq!(instance.tsp.distance(0, 1), 153.0);
+
+        let tour = parse_tour(
+            "NAME : burma14.opt.tour
+TYPE : TOUR
+DIMENSION : 14
+TOUR_SECTION
+1 2 14 3 4 5 6
+12 7 13 8 11 9 10
+-1
+EOF",
+        )
+        .unwrap();
+        assert_eq!(tour[..3], [0, 1, 13]);
+        assert_eq!(instance.tsp.length(&tour), 3323.0);
+
+        let points = "NODE_COORD_SECTION\n1 0 0\n2 3 4\n3 1 1\n4 10 0\nEOF";
+        for (weight_type, expected) in [
+            ("EUC_2D", [5.0, 1.0, 10.0]),
+            ("CEIL_2D", [5.0, 2.0, 10.0]),
+            ("ATT", [2.0, 1.0, 4.0]),
+        ] {
+            let text = format!("DIMENSION: 4\nEDGE_WEIGHT_TYPE: {weight_type}\n{points}");
+            let tsp = parse(&text).unwrap().tsp;
+            let distances = [tsp.distance(0, 1), tsp.distance(0, 2), tsp.distance(0, 3)];
+            assert_eq!(distances, expected, "{weight_type}");
+        }
+
+        for (format, weights) in [
+            ("FULL_MATRIX", "0 1 2\n3 0 4\n5 6 0"),
+            ("UPPER_ROW", "1 2\n4"),
+            ("LOWER_DIAG_ROW", "0 1 0\n2 4 0"),
+        ] {
+            let text = format!(
+                "TYPE: ATSP\nDIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\n\
+                 EDGE_WEIGHT_FORMAT: {format}\nEDGE_WEIGHT_SECTION\n{weights}\nEOF"
+            );
+            let tsp = parse(&text).unwrap().tsp;
+            assert_eq!(tsp.distance(0, 1), 1.0, "{format}");
+            assert_eq!(tsp.distance(1, 2), 4.0, "{format}");
+            assert_eq!(tsp.is_symmetric(), format != "FULL_MATRIX", "{format}");
+        }
+    }
+
+    #[test]
+    fn errors_report_the_line() {
+        let cases = [
+            (BURMA14.replace("GEO", "EUC_3D"), 5),
+            (BURMA14.replace("FUNCTION", "UPPER_COL"), 6),
+            (BURMA14.replace("TYPE: TSP", "TYPE: CVRP"), 2),
+            (BURMA14.replace("DIMENSION: 14", "DIMENSION: many"), 4),
+            (BURMA14.replace("   3  20.09", "   2  20.09"), 11),
+            (BURMA14.replace("98.12", "98,12"), 18),
+            (BURMA14.replace("  14  20.09       94.55\n", ""), 8),
+            (
+                BURMA14.replace("   5  25.23       97.24", "   5  25.23"),
+                13,
+            ),
+            (
+                "DIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\n\
+                 EDGE_WEIGHT_SECTION\n1 2\n4 5\nEOF"
+                    .to_string(),
+                6,
+            ),
+            (
+                "DIMENSION: 3\nEDGE_WEIGHT_TYPE: EXPLICIT\nEDGE_WEIGHT_FORMAT: UPPER_ROW\n\
+                 EDGE_WEIGHT_SECTION\n1\n2\nEOF"
+                    .to_string(),
+                6,
+            ),
+            ("DIMENSION: 3\nEDGE_WEIGHT_TYPE: EUC_2D\nEOF".to_string(), 3),
+        ];
+
+        crate::parse::assert_error_lines(cases, parse);
+
+        let cases = [
+            ("TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1\n2\n2\n-1", 6),
+            ("TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1\n4\n-1", 5),
+            ("TYPE: TOUR\nDIMENSION: 3\nTOUR_SECTION\n1\n2\n-1\nEOF", 6),
+            ("TYPE: TSP\nTOUR_SECTION\n1", 1),
+        ];
+        crate::parse::assert_error_lines(cases, parse_tour);
+    }
+}
@@ -0,0 +1,312 @@
+use rand::distributions::{Distribution, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use crate::metaheuristics::Range;
+
+/// Recombines two parents in place, turning them into two offspring.
+pub trait Crossover {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>;
+}
+
+/// Simulated binary crossover (Deb and Agrawal), bounded version.
+pub struct SimulatedBinary {
+    eta: f64,
+}
+impl SimulatedBinary {
+    pub fn new(eta: f64) -> Self {
+        Self { eta }
+    }
+}
+
+impl Crossover for SimulatedBinary {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        let spread = |beta: f64, u: f64| {
+            let alpha = 2.0 - f64::powf(beta, -(self.eta + 1.0));
+            if u <= 1.0 / alpha {
+                f64::powf(u * alpha, exponent)
+            } else {
+                f64::powf(1.0 / (2.0 - u * alpha), exponent)
+            }
+        };
+
+        for ((x1, x2), range) in a.iter_mut().zip(b.iter_mut()).zip(ranges) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) >= 0.5 {
+                continue;
+            }
+
+            if f64::abs(*x1 - *x2) <= 1e-14 {
+                continue;
+            }
+
+            let (y1, y2) = (f64::min(*x1, *x2), f64::max(*x1, *x2));
+            let u = rng.sample::<f64, _>(&UniformClosedOpen01);
+
+            let beta = 1.0 + 2.0 * f64::max(y1 - range.start, 0.0) / (y2 - y1);
+            let c1 = 0.5 * ((y1 + y2) - spread(beta, u) * (y2 - y1));
+
+            let beta = 1.0 + 2.0 * f64::max(range.end - y2, 0.0) / (y2 - y1);
+            let c2 = 0.5 * ((y1 + y2) + spread(beta, u) * (y2 - y1));
+
+            let c1 = c1.clamp(range.start, range.end);
+            let c2 = c2.clamp(range.start, range.end);
+
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < 0.5 {
+                (*x1, *x2) = (c2, c1);
+            } else {
+                (*x1, *x2) = (c1, c2);
+            }
+        }
+    }
+}
+
+/// Blend crossover, each gene is drawn from the interval spanned by the
+/// parents extended by `alpha` times its length on both sides.
+pub struct BlendAlpha {
+    alpha: f64,
+}
+impl BlendAlpha {
+    pub fn new(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Crossover for BlendAlpha {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        for ((x1, x2), range) in a.iter_mut().zip(b.iter_mut()).zip(ranges) {
+            let (min, max) = (f64::min(*x1, *x2), f64::max(*x1, *x2));
+            let extension = self.alpha * (max - min);
+
+            let distribution = UniformClosedOpen::new(min - extension, max + extension);
+
+            *x1 = rng
+                .sample::<f64, _>(&distribution)
+                .clamp(range.start, range.end);
+            *x2 = rng
+                .sample::<f64, _>(&distribution)
+                .clamp(range.start, range.end);
+        }
+    }
+}
+
+pub struct OnePoint;
+
+impl Crossover for OnePoint {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        _ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        if D < 2 {
+            return;
+        }
+
+        let cut = rng.sample(&UniformClosedOpen::new(1.0, D as f64)) as usize;
+
+        a[cut..].swap_with_slice(&mut b[cut..]);
+    }
+}
+
+pub struct TwoPoint;
+
+impl Crossover for TwoPoint {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        _ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        if D < 2 {
+            return;
+        }
+
+        // the first cut is before the last gene and the second may be after
+        // it, so the segment never holds the first gene but can reach the end,
+        // and is empty when both cuts fall on the same gene
+        let c1 = rng.sample(&UniformClosedOpen::new(1.0, D as f64)) as usize;
+        let c2 = rng.sample(&UniformClosedOpen::new(1.0, (D + 1) as f64)) as usize;
+
+        let (start, end) = (usize::min(c1, c2), usize::max(c1, c2));
+
+        a[start..end].swap_with_slice(&mut b[start..end]);
+    }
+}
+
+pub struct Uniform;
+
+impl Crossover for Uniform {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        _ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        for (x1, x2) in a.iter_mut().zip(b.iter_mut()) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < 0.5 {
+                std::mem::swap(x1, x2);
+            }
+        }
+    }
+}
+
+/// Whole arithmetic crossover, both offspring are convex combinations of the
+/// parents with the same random weight.
+pub struct Arithmetic;
+
+impl Crossover for Arithmetic {
+    fn crossover<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        a: &mut [f64; D],
+        b: &mut [f64; D],
+        _ranges: &[Range<f64>; D],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let w = rng.sample::<f64, _>(&UniformClosedOpen01);
+
+        for (x1, x2) in a.iter_mut().zip(b.iter_mut()) {
+            (*x1, *x2) = ((1.0 - w) * *x1 + w * *x2, w * *x1 + (1.0 - w) * *x2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    const RANGES: [Range<f64>; 4] = [
+        Range::new(-1.0, 1.0),
+        Range::new(0.0, 10.0),
+        Range::new(-5.0, -2.0),
+        Range::new(100.0, 200.0),
+    ];
+
+    // parents at a quarter and three quarters of every range, and the
+    // offspring of `trials` crossovers of them
+    fn offspring<C: Crossover>(crossover: &C, trials: usize) -> Vec<([f64; 4], [f64; 4])> {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+
+        (0..trials)
+            .map(|_| {
+                let mut a = RANGES.map(|r| r.start + 0.25 * (r.end - r.start));
+                let mut b = RANGES.map(|r| r.start + 0.75 * (r.end - r.start));
+                crossover.crossover(&mut rng, &mut a, &mut b, &RANGES);
+                (a, b)
+            })
+            .collect()
+    }
+
+    fn inside(x: &[f64; 4]) -> bool {
+        x.iter()
+            .zip(&RANGES)
+            .all(|(x, r)| r.start <= *x && *x <= r.end)
+    }
+
+    // the genes `a` took from the other parent, after checking that every
+    // gene of the offspring comes from one of the parents
+    fn swapped(a: &[f64; 4], b: &[f64; 4]) -> Vec<usize> {
+        (0..4)
+            .filter(|&i| {
+                let low = RANGES[i].start + 0.25 * (RANGES[i].end - RANGES[i].start);
+                let high = RANGES[i].start + 0.75 * (RANGES[i].end - RANGES[i].start);
+                assert!((a[i], b[i]) == (low, high) || (a[i], b[i]) == (high, low));
+                a[i] == high
+            })
+            .collect()
+    }
+
+    #[test]
+    fn real_coded_crossovers_work() {
+        for (a, b) in offspring(&SimulatedBinary::new(2.0), 1000) {
+            assert!(inside(&a) && inside(&b));
+        }
+
+        for (a, b) in offspring(&BlendAlpha::new(0.5), 1000) {
+            assert!(inside(&a) && inside(&b));
+        }
+
+        // both offspring lie on the segment between the parents, with the
+        // same sum
+        for (a, b) in offspring(&Arithmetic, 1000) {
+            assert!(inside(&a) && inside(&b));
+            for (i, r) in RANGES.iter().enumerate() {
+                assert!((a[i] + b[i] - (r.start + r.end)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn swapping_crossovers_work() {
+        // a suffix starting after the first gene
+        for (a, b) in offspring(&OnePoint, 1000) {
+            let genes = swapped(&a, &b);
+            assert!(!genes.is_empty() && genes[0] >= 1);
+            assert_eq!(genes, (genes[0]..4).collect::<Vec<_>>());
+        }
+
+        // a contiguous run of genes after the first one, which may be empty
+        // and reaches the last gene in some of the trials
+        let mut last = 0;
+        for (a, b) in offspring(&TwoPoint, 1000) {
+            let genes = swapped(&a, &b);
+            if let (Some(&first), Some(&end)) = (genes.first(), genes.last()) {
+                assert!(first >= 1);
+                assert_eq!(genes, (first..=end).collect::<Vec<_>>());
+                last += usize::from(end == 3);
+            }
+        }
+        assert!(last > 0);
+
+        let counts = offspring(&Uniform, 4000)
+            .iter()
+            .flat_map(|(a, b)| swapped(a, b))
+            .fold([0; 4], |mut counts, i| {
+                counts[i] += 1;
+                counts
+            });
+        assert!(counts.iter().all(|&c| (c as f64 - 2000.0).abs() < 150.0));
+    }
+}
@@ -0,0 +1,367 @@
+use rand::distributions::{Distribution, UniformClosedOpen};
+use rand::rngs::{Rng, Xoshiro256Plus};
+
+use super::replacement::sorted_indexes;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{Metaheuristic, Population, Problem, Solution, Termination};
+
+/// Which islands send migrants to which.
+#[derive(Clone, Copy)]
+pub enum Topology {
+    /// Each island sends to the next one.
+    Ring,
+    /// Each island sends to all the others.
+    FullyConnected,
+    /// Each island sends to another one drawn at every migration.
+    Random,
+    /// The first island exchanges migrants with all the others.
+    Star,
+}
+
+#[derive(Clone, Copy)]
+pub enum MigrantSelection {
+    Best,
+    Random,
+}
+
+#[derive(Clone, Copy)]
+pub enum MigrantReplacement {
+    Worst,
+    Random,
+}
+
+pub struct IslandModel<E> {
+    pub algorithm: E,
+    pub islands: usize,
+    /// Number of iterations between migrations.
+    pub migration_interval: usize,
+    pub number_of_migrants: usize,
+    pub topology: Topology,
+    pub migrant_selection: MigrantSelection,
+    pub migrant_replacement: MigrantReplacement,
+}
+
+/// Runs every island on its own thread, with its own stream of `rng`, and
+/// exchanges migrants between them every `migration_interval` iterations.
+/// The streams are `rng` jumped once per island, which leaves `rng` past the
+/// last of them.
+///
+/// Islands only interact during migrations, which are done sequentially with
+/// each island drawing from its own stream, so the result does not depend on
+/// how the threads are scheduled. The evaluation budget of `termination` is
+/// split evenly between the islands.
+///
+/// The observer sees the islands merged into a single population, without
+/// the parameters of the algorithm, once per migration epoch instead of once
+/// per iteration. Stopping the run stops every island.
+pub fn island_model<P, E, O, const D: usize>(
+    rng: &mut Xoshiro256Plus,
+    termination: Termination,
+    parameters: &IslandModel<E>,
+    observer: &mut O,
+) -> Solution<D>
+where
+    P: Problem<D>,
+    E: Metaheuristic + Sync,
+    O: Observer<D>,
+{
+    assert!(parameters.islands > 0, "there must be at least one island");
+    assert!(
+        parameters.migration_interval > 0,
+        "the migration interval must be at least one iteration"
+    );
+
+    let algorithm = &parameters.algorithm;
+
+    let mut rngs = (0..parameters.islands)
+        .map(|_| {
+            let island = rng.clone();
+            rng.jump();
+            island
+        })
+        .collect::<Vec<_>>();
+    let rngs = rngs.as_mut_slice();
+
+    let island_termination = Termination {
+        max_evaluations: termination.max_evaluations / parameters.islands,
+        ..termination
+    };
+
+    let mut populations = std::thread::scope(|scope| {
+        let handles = rngs
+            .iter_mut()
+            .map(|rng| scope.spawn(|| algorithm.initialize::<Xoshiro256Plus, P, D>(rng)))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    observer.initialize(&merge(&populations));
+
+    loop {
+        std::thread::scope(|scope| {
+            for (rng, population) in rngs.iter_mut().zip(&mut populations) {
+                scope.spawn(|| {
+                    for _ in 0..parameters.migration_interval {
+                        if population.is_done(&island_termination) {
+                            break;
+                        }
+                        algorithm.iterate::<Xoshiro256Plus, P, D>(
+                            rng,
+                            population,
+                            &island_termination,
+                        );
+                    }
+                });
+            }
+        });
+
+        let all_done = populations
+            .iter()
+            .all(|population| population.is_done(&island_termination));
+        let target_reached = populations
+            .iter()
+            .any(|population| population.best_fitness <= termination.target);
+
+        let done = all_done || target_reached;
+        if !done {
+            migrate(rngs, &mut populations, parameters);
+        }
+
+        if observer.generation(&merge(&populations)).is_break() || done {
+            break;
+        }
+    }
+
+    let population = merge(&populations);
+    observer.terminate(&population);
+
+    population.solution()
+}
+
+// the islands as a single population, with the best point of all of them,
+// the iterations of the furthest one and the evaluations of all
+fn merge<const D: usize>(populations: &[Population<D>]) -> Population<D> {
+    let best = populations
+        .iter()
+        .min_by(|a, b| a.best_fitness.partial_cmp(&b.best_fitness).unwrap())
+        .unwrap();
+
+    Population {
+        individuals: populations
+            .iter()
+            .flat_map(|p| p.individuals.iter().copied())
+            .collect(),
+        fitnesses: populations
+            .iter()
+            .flat_map(|p| p.fitnesses.iter().copied())
+            .collect(),
+        ages: populations
+            .iter()
+            .flat_map(|p| p.ages.iter().copied())
+            .collect(),
+        best_individual: best.best_individual,
+        best_fitness: best.best_fitness,
+        iterations: populations.iter().map(|p| p.iterations).max().unwrap(),
+        evaluations: populations.iter().map(|p| p.evaluations).sum(),
+        parameters: Vec::new(),
+    }
+}
+
+fn migrate<R, E, const D: usize>(
+    rngs: &mut [R],
+    populations: &mut [Population<D>],
+    parameters: &IslandModel<E>,
+) where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+{
+    let n = populations.len();
+    if n < 2 {
+        return;
+    }
+
+    // (source, destination) pairs
+    let routes = match parameters.topology {
+        Topology::Ring => (0..n).map(|i| (i, (i + 1) % n)).collect::<Vec<_>>(),
+        Topology::FullyConnected => (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .collect(),
+        Topology::Random => (0..n)
+            .map(|i| {
+                let j = rngs[i].sample(&UniformClosedOpen::new(0 as f64, (n - 1) as f64)) as usize;
+                (i, if j >= i { j + 1 } else { j })
+            })
+            .collect(),
+        Topology::Star => (1..n).flat_map(|i| [(i, 0), (0, i)]).collect(),
+    };
+
+    // all migrants leave before any of them arrives
+    let migrants = routes
+        .iter()
+        .map(|&(source, _)| {
+            let population = &populations[source];
+            let m = usize::min(parameters.number_of_migrants, population.individuals.len());
+
+            let chosen = match parameters.migrant_selection {
+                MigrantSelection::Best => sorted_indexes(&population.fitnesses)[..m].to_vec(),
+                MigrantSelection::Random => {
+                    let mut indexes = (0..population.individuals.len()).collect::<Vec<_>>();
+                    rand::util::partial_shuffle(&mut rngs[source], &mut indexes, m).to_vec()
+                }
+            };
+
+            chosen
+                .into_iter()
+                .map(|i| (population.individuals[i], population.fitnesses[i]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for (&(_, destination), migrants) in routes.iter().zip(migrants) {
+        let population = &mut populations[destination];
+        let m = usize::min(migrants.len(), population.individuals.len());
+
+        let replaced = match parameters.migrant_replacement {
+            MigrantReplacement::Worst => {
+                let mut order = sorted_indexes(&population.fitnesses);
+                order.reverse();
+                order[..m].to_vec()
+            }
+            MigrantReplacement::Random => {
+                let mut indexes = (0..population.individuals.len()).collect::<Vec<_>>();
+                rand::util::partial_shuffle(&mut rngs[destination], &mut indexes, m).to_vec()
+            }
+        };
+
+        for (i, (individual, fitness)) in replaced.into_iter().zip(migrants) {
+            population.individuals[i] = individual;
+            population.fitnesses[i] = fitness;
+            population.ages[i] = 0;
+
+            population.update_best(&individual, fitness);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::*;
+    use crate::metaheuristics::evolutionary_computation::{
+        Arithmetic, GeneticAlgorithm, Replacement, Tournament, UniformReset,
+    };
+    use crate::metaheuristics::observer::BestFitness;
+    use crate::metaheuristics::tests::Sphere;
+
+    fn islands() -> IslandModel<GeneticAlgorithm<Tournament, Arithmetic, UniformReset>> {
+        IslandModel {
+            algorithm: GeneticAlgorithm {
+                population_size: 20,
+                selection: Tournament::new(2),
+                crossover: Arithmetic,
+                crossover_probability: 0.9,
+                mutation: UniformReset::new(0.1),
+                replacement: Replacement::Generational { elitism: 1 },
+                workers: 1,
+            },
+            islands: 4,
+            migration_interval: 5,
+            number_of_migrants: 2,
+            topology: Topology::Random,
+            migrant_selection: MigrantSelection::Random,
+            migrant_replacement: MigrantReplacement::Random,
+        }
+    }
+
+    #[test]
+    fn island_model_is_reproducible() {
+        let parameters = islands();
+        let solve = || {
+            let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+            island_model::<Sphere, _, _, 4>(
+                &mut rng,
+                Termination::iterations(50),
+                &parameters,
+                &mut (),
+            )
+        };
+
+        let solution = solve();
+        assert_eq!(solution.iterations, 50);
+        for _ in 0..5 {
+            assert_eq!(solve(), solution);
+        }
+    }
+
+    // the merged populations seen by the observer, stopping after `epochs`
+    struct Epochs {
+        epochs: usize,
+        seen: Vec<Population<4>>,
+    }
+
+    impl Observer<4> for Epochs {
+        fn generation(&mut self, population: &Population<4>) -> ControlFlow<()> {
+            self.seen.push(population.clone());
+            if self.seen.len() == self.epochs {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    #[test]
+    fn island_model_is_observed() {
+        let parameters = islands();
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut observers = (
+            BestFitness::new(),
+            Epochs {
+                epochs: usize::MAX,
+                seen: Vec::new(),
+            },
+        );
+        let solution = island_model::<Sphere, _, _, 4>(
+            &mut rng,
+            Termination::iterations(50),
+            &parameters,
+            &mut observers,
+        );
+        let (best, Epochs { seen, .. }) = observers;
+
+        // the initial islands and one merged population per epoch
+        assert_eq!(best.history.len(), 1 + 10);
+        assert_eq!(*best.history.last().unwrap(), solution.fitness);
+        assert!(best.history.windows(2).all(|w| w[1] <= w[0]));
+
+        assert_eq!(seen.len(), 10);
+        for (epoch, population) in seen.iter().enumerate() {
+            assert_eq!(population.individuals.len(), 4 * 20);
+            assert_eq!(population.fitnesses.len(), 4 * 20);
+            assert_eq!(population.iterations, 5 * (epoch + 1));
+            assert_eq!(population.evaluations, 4 * 20 * (1 + 5 * (epoch + 1)));
+            assert!(population
+                .fitnesses
+                .iter()
+                .all(|&f| f >= population.best_fitness));
+        }
+
+        // stopping the observer stops every island
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let solution = island_model::<Sphere, _, _, 4>(
+            &mut rng,
+            Termination::iterations(50),
+            &parameters,
+            &mut Epochs {
+                epochs: 3,
+                seen: Vec::new(),
+            },
+        );
+        assert_eq!(solution.iterations, 15);
+        assert_eq!(solution.evaluations, 4 * 20 * 16);
+    }
+}
@@ -0,0 +1,209 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{Crossover, GeneticAlgorithm, Mutation, Selection};
+use crate::metaheuristics::local_search::LocalSearch;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{run, Metaheuristic, Population, Problem, Solution, Termination};
+
+/// What is kept from a local search.
+#[derive(Clone, Copy)]
+pub enum Learning {
+    /// The improved genome replaces the original one.
+    Lamarckian,
+    /// Only the improved fitness is kept, the genome is left untouched.
+    Baldwinian,
+}
+
+pub struct MemeticAlgorithm<S, C, M, L> {
+    pub genetic_algorithm: GeneticAlgorithm<S, C, M>,
+    pub local_search: L,
+    /// Probability of each offspring being refined.
+    pub fraction: f64,
+    /// Maximum number of evaluations of a single local search.
+    pub local_evaluations: usize,
+    pub learning: Learning,
+}
+
+/// A genetic algorithm whose offspring are refined by a local search. The
+/// evaluations spent by the local searches count towards `termination`.
+pub fn memetic_algorithm<R, P, S, C, M, L, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &MemeticAlgorithm<S, C, M, L>,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    S: Selection,
+    C: Crossover,
+    M: Mutation,
+    L: LocalSearch,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl<S, C, M, L> Metaheuristic for MemeticAlgorithm<S, C, M, L>
+where
+    S: Selection,
+    C: Crossover,
+    M: Mutation,
+    L: LocalSearch,
+{
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        self.genetic_algorithm.initialize::<R, P, D>(rng)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let MemeticAlgorithm {
+            ref genetic_algorithm,
+            ref local_search,
+            fraction,
+            local_evaluations,
+            learning,
+        } = *self;
+
+        genetic_algorithm.iterate_with::<R, P, _, D>(
+            rng,
+            population,
+            termination,
+            |rng, offspring, fitnesses, population| {
+                for (individual, fitness) in offspring.iter_mut().zip(fitnesses) {
+                    if rng.sample::<f64, _>(&UniformClosedOpen01) >= fraction {
+                        continue;
+                    }
+
+                    let budget = usize::min(
+                        local_evaluations,
+                        termination
+                            .max_evaluations
+                            .saturating_sub(population.evaluations),
+                    );
+                    if budget == 0 {
+                        break;
+                    }
+
+                    let refined = local_search.refine::<R, P, D>(
+                        rng,
+                        *individual,
+                        *fitness,
+                        Termination::evaluations(budget).with_target(termination.target),
+                    );
+                    population.evaluations += refined.evaluations;
+
+                    if refined.fitness < *fitness {
+                        if let Learning::Lamarckian = learning {
+                            *individual = refined.x;
+                        }
+                        *fitness = refined.fitness;
+                    }
+
+                    population.update_best(&refined.x, refined.fitness);
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::evolutionary_computation::{
+        Polynomial, Replacement, SimulatedBinary, Tournament,
+    };
+    use crate::metaheuristics::local_search::{HillClimbing, Neighborhood};
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn memetic(
+        learning: Learning,
+    ) -> MemeticAlgorithm<Tournament, SimulatedBinary, Polynomial, HillClimbing> {
+        MemeticAlgorithm {
+            genetic_algorithm: GeneticAlgorithm {
+                population_size: 10,
+                selection: Tournament::new(2),
+                crossover: SimulatedBinary::new(15.0),
+                crossover_probability: 0.9,
+                mutation: Polynomial::new(0.25, 20.0),
+                replacement: Replacement::Generational { elitism: 0 },
+                workers: 1,
+            },
+            local_search: HillClimbing::new(Neighborhood::Gaussian { sigma: 0.05 }, 20),
+            fraction: 1.0,
+            local_evaluations: 30,
+            learning,
+        }
+    }
+
+    #[test]
+    fn learning_works() {
+        let termination = Termination::evaluations(100_000);
+
+        for learning in [Learning::Lamarckian, Learning::Baldwinian] {
+            let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+            let parameters = memetic(learning);
+            let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+            let genome_fitnesses = population
+                .individuals
+                .iter()
+                .map(Sphere::phi)
+                .collect::<Vec<_>>();
+
+            match learning {
+                // the refined genomes were kept along with their fitnesses
+                Learning::Lamarckian => assert_eq!(population.fitnesses, genome_fitnesses),
+                // only the fitnesses were, and they are better than the genomes
+                Learning::Baldwinian => {
+                    let pairs = population.fitnesses.iter().zip(&genome_fitnesses);
+                    assert!(pairs.clone().all(|(learned, genome)| learned <= genome));
+                    assert!(pairs.clone().any(|(learned, genome)| learned < genome));
+                }
+            }
+
+            // the best is the refined point either way
+            assert_eq!(
+                population.best_fitness,
+                Sphere::phi(&population.best_individual)
+            );
+        }
+    }
+
+    #[test]
+    fn local_evaluations_count() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let solution = memetic_algorithm::<_, Sphere, _, _, _, _, _, 4>(
+            &mut rng,
+            Termination::evaluations(1000),
+            &memetic(Learning::Lamarckian),
+            &mut (),
+        );
+
+        // the budget is reached long before the genetic algorithm alone would
+        // have spent it, and the local searches never go over it
+        assert!(solution.iterations < 1000 / 10 / 2);
+        assert!((1000..1000 + 10).contains(&solution.evaluations));
+    }
+}
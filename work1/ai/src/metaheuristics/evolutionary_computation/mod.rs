@@ -0,0 +1,308 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::observer::Observer;
+use super::{evaluate, run, Metaheuristic, Population, Problem, Range, Solution, Termination};
+
+mod crossover;
+mod island;
+mod memetic;
+mod mutation;
+mod replacement;
+mod selection;
+
+pub use crossover::{
+    Arithmetic, BlendAlpha, Crossover, OnePoint, SimulatedBinary, TwoPoint, Uniform,
+};
+pub use island::{island_model, IslandModel, MigrantReplacement, MigrantSelection, Topology};
+pub use memetic::{memetic_algorithm, Learning, MemeticAlgorithm};
+pub use mutation::{Gaussian, Mutation, NonUniform, Polynomial, UniformReset};
+pub use replacement::{Replacement, SteadyStatePolicy};
+pub use selection::{
+    Rank, RouletteWheel, Selection, StochasticUniversalSampling, Tournament, Truncation,
+};
+
+pub struct GeneticAlgorithm<S, C, M> {
+    pub population_size: usize,
+    pub selection: S,
+    pub crossover: C,
+    pub crossover_probability: f64,
+    pub mutation: M,
+    pub replacement: Replacement,
+    /// Number of threads evaluating the offspring.
+    pub workers: usize,
+}
+
+pub fn genetic_algorithm<R, P, S, C, M, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &GeneticAlgorithm<S, C, M>,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    S: Selection,
+    C: Crossover,
+    M: Mutation,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl<S, C, M> Metaheuristic for GeneticAlgorithm<S, C, M>
+where
+    S: Selection,
+    C: Crossover,
+    M: Mutation,
+{
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        Population::random::<R, P>(rng, self.population_size, self.workers)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        self.iterate_with::<R, P, _, D>(rng, population, termination, |_, _, _, _| {});
+    }
+}
+
+impl<S, C, M> GeneticAlgorithm<S, C, M>
+where
+    S: Selection,
+    C: Crossover,
+    M: Mutation,
+{
+    // the generational step shared by the genetic and memetic algorithms,
+    // where `improve` may modify the offspring after they are evaluated
+    fn iterate_with<R, P, I, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+        mut improve: I,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+        I: FnMut(&mut R, &mut [[f64; D]], &mut [f64], &mut Population<D>),
+    {
+        let population_size = population.individuals.len();
+
+        let number_of_offspring = self.replacement.number_of_offspring(population_size);
+        let number_of_steps = self.replacement.number_of_steps(population_size);
+
+        // steady-state needs two parents even when a single offspring is kept
+        let number_of_parents = usize::max(number_of_offspring, 2);
+
+        let mut offspring = vec![[0.0; D]; number_of_parents];
+        let mut offspring_fitnesses = vec![0.0; number_of_parents];
+
+        let mut parents = vec![0; number_of_parents];
+
+        let progress = termination.progress(population.iterations, population.evaluations);
+
+        for _ in 0..number_of_steps {
+            // selection
+            self.selection
+                .select(rng, &population.fitnesses, &mut parents);
+            for (individual, &j) in offspring.iter_mut().zip(&parents) {
+                *individual = population.individuals[j];
+            }
+
+            // recombination
+            if let Replacement::SteadyState { .. } = self.replacement {
+                if rng.sample::<f64, _>(&UniformClosedOpen01) < self.crossover_probability {
+                    let (a, b) = offspring.split_at_mut(1);
+                    self.crossover
+                        .crossover(rng, &mut a[0], &mut b[0], &P::RANGES);
+                }
+            } else {
+                recombine(
+                    rng,
+                    &mut offspring,
+                    &self.crossover,
+                    self.crossover_probability,
+                    &P::RANGES,
+                );
+            }
+
+            let offspring = &mut offspring[..number_of_offspring];
+            let offspring_fitnesses = &mut offspring_fitnesses[..number_of_offspring];
+
+            // mutation
+            for individual in offspring.iter_mut() {
+                self.mutation.mutate(rng, individual, &P::RANGES, progress);
+            }
+
+            // evaluation
+            evaluate::<P, D>(offspring, offspring_fitnesses, self.workers);
+            population.evaluations += number_of_offspring;
+
+            for (individual, &fitness) in offspring.iter().zip(offspring_fitnesses.iter()) {
+                population.update_best(individual, fitness);
+            }
+
+            improve(rng, offspring, offspring_fitnesses, population);
+
+            // replacement
+            self.replacement.replace(
+                &mut population.individuals,
+                &mut population.fitnesses,
+                &mut population.ages,
+                offspring,
+                offspring_fitnesses,
+                &P::RANGES,
+            );
+        }
+
+        population.iterations += 1;
+    }
+}
+
+// each individual takes part in a crossover with probability
+// `crossover_probability`, paired with the next one chosen
+fn recombine<R, C, const D: usize>(
+    rng: &mut R,
+    population: &mut [[f64; D]],
+    crossover: &C,
+    crossover_probability: f64,
+    ranges: &[Range<f64>; D],
+) where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    C: Crossover,
+{
+    let mut first = None;
+
+    for i in 0..population.len() {
+        if rng.sample::<f64, _>(&UniformClosedOpen01) >= crossover_probability {
+            continue;
+        }
+
+        match first.take() {
+            None => first = Some(i),
+            Some(p1) => {
+                let (head, tail) = population.split_at_mut(i);
+                crossover.crossover(rng, &mut head[p1], &mut tail[0], ranges);
+            }
+        }
+    }
+}
+
+pub struct DifferentialEvolution {
+    pub population_size: usize,
+    pub crossover_probability: f64,
+    pub differential_weight: f64,
+    /// Number of threads evaluating the trial vectors.
+    pub workers: usize,
+}
+
+pub fn differential_evolution<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &DifferentialEvolution,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for DifferentialEvolution {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        Population::random::<R, P>(rng, self.population_size, self.workers)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let DifferentialEvolution {
+            crossover_probability,
+            differential_weight,
+            workers,
+            ..
+        } = *self;
+
+        let population_size = population.individuals.len();
+        let individuals = &population.individuals;
+
+        let mut new_population = vec![[0.0; D]; population_size];
+        let mut new_fitnesses = vec![0.0; population_size];
+
+        let mut indexes = (0..population_size).collect::<Vec<_>>();
+        rand::util::shuffle(rng, &mut indexes);
+
+        for i in 0..population_size {
+            let (r1, r2, r3) = (
+                indexes[i % population_size],
+                indexes[(i + 1) % population_size],
+                indexes[(i + 2) % population_size],
+            );
+
+            let r = rng.sample(&UniformClosedOpen::new(0 as f64, D as f64)) as usize;
+            for j in 0..D {
+                let rj = rng.sample::<f64, _>(&UniformClosedOpen01);
+
+                if rj < crossover_probability || j == r {
+                    new_population[i][j] = individuals[r1][j]
+                        + differential_weight * (individuals[r2][j] - individuals[r3][j]);
+                } else {
+                    new_population[i][j] = individuals[i][j];
+                }
+            }
+        }
+
+        evaluate::<P, D>(&new_population, &mut new_fitnesses, workers);
+        population.evaluations += population_size;
+
+        for i in 0..population_size {
+            if new_fitnesses[i] < population.fitnesses[i] {
+                population.individuals[i] = new_population[i];
+                population.fitnesses[i] = new_fitnesses[i];
+
+                population.update_best(&new_population[i], new_fitnesses[i]);
+            }
+        }
+
+        population.iterations += 1;
+    }
+}
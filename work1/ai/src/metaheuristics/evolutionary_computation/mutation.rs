@@ -0,0 +1,245 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use crate::metaheuristics::Range;
+
+/// Perturbs an individual in place. `progress` goes from 0 at the start to 1
+/// at the end of the run, for operators whose strength decays over time.
+pub trait Mutation {
+    fn mutate<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        individual: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+        progress: f64,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>;
+}
+
+/// Polynomial mutation (Deb and Goyal), bounded version.
+pub struct Polynomial {
+    probability: f64,
+    eta: f64,
+}
+impl Polynomial {
+    pub fn new(probability: f64, eta: f64) -> Self {
+        Self { probability, eta }
+    }
+}
+
+impl Mutation for Polynomial {
+    fn mutate<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        individual: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+        _progress: f64,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    {
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        for (x, range) in individual.iter_mut().zip(ranges) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) >= self.probability {
+                continue;
+            }
+
+            let width = range.end - range.start;
+            let delta1 = ((*x - range.start) / width).clamp(0.0, 1.0);
+            let delta2 = ((range.end - *x) / width).clamp(0.0, 1.0);
+
+            let u = rng.sample::<f64, _>(&UniformClosedOpen01);
+
+            let deltaq = if u < 0.5 {
+                let value = 2.0 * u + (1.0 - 2.0 * u) * f64::powf(1.0 - delta1, self.eta + 1.0);
+                f64::powf(value, exponent) - 1.0
+            } else {
+                let value =
+                    2.0 * (1.0 - u) + 2.0 * (u - 0.5) * f64::powf(1.0 - delta2, self.eta + 1.0);
+                1.0 - f64::powf(value, exponent)
+            };
+
+            *x = (*x + deltaq * width).clamp(range.start, range.end);
+        }
+    }
+}
+
+/// Adds normal noise with standard deviation `sigma` times the width of the
+/// range of each gene.
+pub struct Gaussian {
+    probability: f64,
+    sigma: f64,
+}
+impl Gaussian {
+    pub fn new(probability: f64, sigma: f64) -> Self {
+        Self { probability, sigma }
+    }
+}
+
+impl Mutation for Gaussian {
+    fn mutate<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        individual: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+        _progress: f64,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    {
+        for (x, range) in individual.iter_mut().zip(ranges) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) >= self.probability {
+                continue;
+            }
+
+            let std_dev = self.sigma * (range.end - range.start);
+            *x = (*x + rng.sample::<f64, _>(&Normal::new(0.0, std_dev)))
+                .clamp(range.start, range.end);
+        }
+    }
+}
+
+/// Non-uniform mutation (Michalewicz), the step shrinks towards zero as the
+/// run progresses, at a rate controlled by `b`.
+pub struct NonUniform {
+    probability: f64,
+    b: f64,
+}
+impl NonUniform {
+    pub fn new(probability: f64, b: f64) -> Self {
+        Self { probability, b }
+    }
+}
+
+impl Mutation for NonUniform {
+    fn mutate<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        individual: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+        progress: f64,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    {
+        let exponent = f64::powf(1.0 - progress.clamp(0.0, 1.0), self.b);
+
+        for (x, range) in individual.iter_mut().zip(ranges) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) >= self.probability {
+                continue;
+            }
+
+            let up = rng.sample::<f64, _>(&UniformClosedOpen01) < 0.5;
+            let r = rng.sample::<f64, _>(&UniformClosedOpen01);
+            let shrink = 1.0 - f64::powf(r, exponent);
+
+            if up {
+                *x += (range.end - *x) * shrink;
+            } else {
+                *x -= (*x - range.start) * shrink;
+            }
+        }
+    }
+}
+
+/// Replaces genes by values drawn uniformly from their range.
+pub struct UniformReset {
+    probability: f64,
+}
+impl UniformReset {
+    pub fn new(probability: f64) -> Self {
+        Self { probability }
+    }
+}
+
+impl Mutation for UniformReset {
+    fn mutate<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        individual: &mut [f64; D],
+        ranges: &[Range<f64>; D],
+        _progress: f64,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    {
+        for (x, range) in individual.iter_mut().zip(ranges) {
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < self.probability {
+                *x = rng.sample(&UniformClosedOpen::new(range.start, range.end));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    const RANGES: [Range<f64>; 3] = [
+        Range::new(-1.0, 1.0),
+        Range::new(0.0, 10.0),
+        Range::new(100.0, 200.0),
+    ];
+
+    // `trials` mutations of the point near the lower bound of every range,
+    // where clamping matters most
+    fn mutants<M: Mutation>(mutation: &M, trials: usize, progress: f64) -> Vec<[f64; 3]> {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+
+        (0..trials)
+            .map(|_| {
+                let mut x = RANGES.map(|r| r.start + 0.01 * (r.end - r.start));
+                mutation.mutate(&mut rng, &mut x, &RANGES, progress);
+                x
+            })
+            .collect()
+    }
+
+    fn inside(x: &[f64; 3]) -> bool {
+        x.iter()
+            .zip(&RANGES)
+            .all(|(x, r)| r.start <= *x && *x <= r.end)
+    }
+
+    #[test]
+    fn mutations_work() {
+        let start = RANGES.map(|r| r.start + 0.01 * (r.end - r.start));
+
+        assert!(mutants(&Polynomial::new(1.0, 20.0), 1000, 0.0)
+            .iter()
+            .all(inside));
+        assert!(mutants(&Gaussian::new(1.0, 0.5), 1000, 0.0)
+            .iter()
+            .all(inside));
+        assert!(mutants(&NonUniform::new(1.0, 2.0), 1000, 0.5)
+            .iter()
+            .all(inside));
+
+        // the steps of non-uniform mutation vanish at the end of the run
+        assert!(mutants(&NonUniform::new(1.0, 2.0), 100, 1.0)
+            .iter()
+            .all(|x| *x == start));
+
+        // nothing changes with a probability of 0
+        assert!(mutants(&Gaussian::new(0.0, 0.5), 100, 0.0)
+            .iter()
+            .all(|x| *x == start));
+
+        // uniform resets cover the whole range, with its center as the mean
+        let resets = mutants(&UniformReset::new(1.0), 10000, 0.0);
+        assert!(resets.iter().all(inside));
+        for (i, r) in RANGES.iter().enumerate() {
+            let mean = resets.iter().map(|x| x[i]).sum::<f64>() / resets.len() as f64;
+            let width = r.end - r.start;
+            assert!((mean - (r.start + r.end) / 2.0).abs() < 0.01 * width);
+        }
+    }
+}
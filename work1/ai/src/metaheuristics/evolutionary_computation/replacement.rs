@@ -0,0 +1,326 @@
+use crate::metaheuristics::Range;
+
+/// How the offspring of a generation are merged into the population.
+#[derive(Clone, Copy)]
+pub enum Replacement {
+    /// The offspring replace the whole population, except for the worst
+    /// `elitism` ones, whose places are taken by the best parents.
+    Generational { elitism: usize },
+    /// `lambda` offspring are bred and the best individuals among parents and
+    /// offspring survive.
+    MuPlusLambda { lambda: usize },
+    /// `lambda` offspring are bred and only the best of them survive, so
+    /// `lambda` must not be smaller than the population size.
+    MuCommaLambda { lambda: usize },
+    /// Each step breeds `offspring` (one or two) individuals and inserts them
+    /// into the population according to `policy`. An iteration takes as many
+    /// steps as needed to breed a population worth of offspring.
+    SteadyState {
+        offspring: usize,
+        policy: SteadyStatePolicy,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub enum SteadyStatePolicy {
+    ReplaceWorst,
+    ReplaceOldest,
+    /// The offspring replaces the individual closest to it, but only if it is
+    /// better, which helps preserving niches.
+    Crowding,
+}
+
+impl Replacement {
+    pub(super) fn number_of_offspring(&self, population_size: usize) -> usize {
+        match *self {
+            Self::Generational { .. } => population_size,
+            Self::MuPlusLambda { lambda } => lambda,
+            Self::MuCommaLambda { lambda } => {
+                assert!(lambda >= population_size, "(μ,λ) requires λ ≥ μ");
+                lambda
+            }
+            Self::SteadyState { offspring, .. } => {
+                assert!(
+                    offspring == 1 || offspring == 2,
+                    "steady-state breeds one or two offspring per step"
+                );
+                offspring
+            }
+        }
+    }
+
+    pub(super) fn number_of_steps(&self, population_size: usize) -> usize {
+        match *self {
+            Self::SteadyState { offspring, .. } => usize::max(population_size / offspring, 1),
+            _ => 1,
+        }
+    }
+
+    pub(super) fn replace<const D: usize>(
+        &self,
+        population: &mut [[f64; D]],
+        fitnesses: &mut [f64],
+        ages: &mut [usize],
+        offspring: &[[f64; D]],
+        offspring_fitnesses: &[f64],
+        ranges: &[Range<f64>; D],
+    ) {
+        let population_size = population.len();
+
+        match *self {
+            Self::Generational { elitism } => {
+                let elitism = usize::min(elitism, population_size);
+
+                let parents = sorted_indexes(fitnesses);
+                let children = sorted_indexes(offspring_fitnesses);
+
+                let survivors = parents[..elitism]
+                    .iter()
+                    .map(|&i| (population[i], fitnesses[i]))
+                    .chain(
+                        children[..population_size - elitism]
+                            .iter()
+                            .map(|&i| (offspring[i], offspring_fitnesses[i])),
+                    )
+                    .collect::<Vec<_>>();
+
+                overwrite(population, fitnesses, survivors);
+            }
+            Self::MuPlusLambda { .. } => {
+                let candidates = population
+                    .iter()
+                    .copied()
+                    .zip(fitnesses.iter().copied())
+                    .chain(
+                        offspring
+                            .iter()
+                            .copied()
+                            .zip(offspring_fitnesses.iter().copied()),
+                    )
+                    .collect::<Vec<_>>();
+
+                let order = sorted_indexes(&candidates.iter().map(|&(_, f)| f).collect::<Vec<_>>());
+
+                let survivors = order[..population_size]
+                    .iter()
+                    .map(|&i| candidates[i])
+                    .collect::<Vec<_>>();
+
+                overwrite(population, fitnesses, survivors);
+            }
+            Self::MuCommaLambda { .. } => {
+                let children = sorted_indexes(offspring_fitnesses);
+
+                let survivors = children[..population_size]
+                    .iter()
+                    .map(|&i| (offspring[i], offspring_fitnesses[i]))
+                    .collect::<Vec<_>>();
+
+                overwrite(population, fitnesses, survivors);
+            }
+            Self::SteadyState { policy, .. } => {
+                for age in ages.iter_mut() {
+                    *age += 1;
+                }
+
+                for (child, &child_fitness) in offspring.iter().zip(offspring_fitnesses) {
+                    let target = match policy {
+                        SteadyStatePolicy::ReplaceWorst => {
+                            let (target, _) = fitnesses
+                                .iter()
+                                .enumerate()
+                                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                                .unwrap();
+                            target
+                        }
+                        SteadyStatePolicy::ReplaceOldest => {
+                            let (target, _) = ages
+                                .iter()
+                                .enumerate()
+                                .rev()
+                                .max_by_key(|&(_, age)| age)
+                                .unwrap();
+                            target
+                        }
+                        SteadyStatePolicy::Crowding => {
+                            let (target, _) = population
+                                .iter()
+                                .map(|individual| distance(individual, child, ranges))
+                                .enumerate()
+                                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                                .unwrap();
+
+                            if child_fitness >= fitnesses[target] {
+                                continue;
+                            }
+                            target
+                        }
+                    };
+
+                    population[target] = *child;
+                    fitnesses[target] = child_fitness;
+                    ages[target] = 0;
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn sorted_indexes(fitnesses: &[f64]) -> Vec<usize> {
+    let mut order = (0..fitnesses.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| fitnesses[a].partial_cmp(&fitnesses[b]).unwrap());
+    order
+}
+
+fn overwrite<const D: usize>(
+    population: &mut [[f64; D]],
+    fitnesses: &mut [f64],
+    survivors: Vec<([f64; D], f64)>,
+) {
+    for ((individual, fitness), (survivor, survivor_fitness)) in
+        population.iter_mut().zip(fitnesses).zip(survivors)
+    {
+        *individual = survivor;
+        *fitness = survivor_fitness;
+    }
+}
+
+// euclidean distance with every coordinate normalized by the width of its range
+fn distance<const D: usize>(a: &[f64; D], b: &[f64; D], ranges: &[Range<f64>; D]) -> f64 {
+    a.iter()
+        .zip(b)
+        .zip(ranges)
+        .map(|((x, y), range)| {
+            let d = (x - y) / (range.end - range.start);
+            d * d
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGES: [Range<f64>; 1] = [Range::new(0.0, 10.0)];
+
+    // the individuals of a one-dimensional population are their fitnesses,
+    // and the result is the population after `replacement` and its ages
+    fn replace(
+        replacement: Replacement,
+        population: &[f64],
+        ages: &[usize],
+        offspring: &[f64],
+    ) -> (Vec<f64>, Vec<usize>) {
+        let mut individuals = population.iter().map(|&x| [x]).collect::<Vec<_>>();
+        let mut fitnesses = population.to_vec();
+        let mut ages = ages.to_vec();
+        let children = offspring.iter().map(|&x| [x]).collect::<Vec<_>>();
+
+        replacement.replace(
+            &mut individuals,
+            &mut fitnesses,
+            &mut ages,
+            &children,
+            offspring,
+            &RANGES,
+        );
+
+        assert!(individuals.iter().zip(&fitnesses).all(|(x, f)| x[0] == *f));
+        (fitnesses, ages)
+    }
+
+    #[test]
+    fn generational_replacement_works() {
+        let population = [5.0, 1.0, 3.0, 2.0];
+        let offspring = [10.0, 7.0, 8.0, 9.0];
+
+        let (fitnesses, _) = replace(
+            Replacement::Generational { elitism: 0 },
+            &population,
+            &[0; 4],
+            &offspring,
+        );
+        assert_eq!(fitnesses, [7.0, 8.0, 9.0, 10.0]);
+
+        // the elites survive and the worst offspring make room for them
+        let (fitnesses, _) = replace(
+            Replacement::Generational { elitism: 2 },
+            &population,
+            &[0; 4],
+            &offspring,
+        );
+        assert_eq!(fitnesses, [1.0, 2.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    fn mu_lambda_replacements_work() {
+        let population = [5.0, 1.0, 3.0];
+        let offspring = [4.0, 0.5, 6.0, 2.0, 9.0];
+
+        let (fitnesses, _) = replace(
+            Replacement::MuPlusLambda { lambda: 5 },
+            &population,
+            &[0; 3],
+            &offspring,
+        );
+        assert_eq!(fitnesses, [0.5, 1.0, 2.0]);
+
+        // the parents never survive, even the best one
+        let (fitnesses, _) = replace(
+            Replacement::MuCommaLambda { lambda: 5 },
+            &population,
+            &[0; 3],
+            &offspring,
+        );
+        assert_eq!(fitnesses, [0.5, 2.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(μ,λ) requires λ ≥ μ")]
+    fn mu_comma_lambda_needs_enough_offspring() {
+        Replacement::MuCommaLambda { lambda: 2 }.number_of_offspring(3);
+    }
+
+    #[test]
+    fn steady_state_replacements_work() {
+        let population = [5.0, 1.0, 3.0, 2.0];
+        let ages = [2, 7, 1, 7];
+
+        let steady_state = |policy| Replacement::SteadyState {
+            offspring: 2,
+            policy,
+        };
+
+        let (fitnesses, ages_after) = replace(
+            steady_state(SteadyStatePolicy::ReplaceWorst),
+            &population,
+            &ages,
+            &[4.0, 6.0],
+        );
+        assert_eq!(fitnesses, [6.0, 1.0, 3.0, 2.0]);
+        assert_eq!(ages_after, [0, 8, 2, 8]);
+
+        // the oldest individuals go first, whatever their fitness, and the
+        // first of them on ties
+        let (fitnesses, ages_after) = replace(
+            steady_state(SteadyStatePolicy::ReplaceOldest),
+            &population,
+            &ages,
+            &[4.0, 6.0],
+        );
+        assert_eq!(fitnesses, [5.0, 4.0, 3.0, 6.0]);
+        assert_eq!(ages_after, [3, 0, 2, 0]);
+
+        // 2.7 is better than its nearest individual, 3, and 5.5 is worse
+        // than 5
+        let (fitnesses, ages_after) = replace(
+            steady_state(SteadyStatePolicy::Crowding),
+            &population,
+            &ages,
+            &[2.7, 5.5],
+        );
+        assert_eq!(fitnesses, [5.0, 1.0, 2.7, 2.0]);
+        assert_eq!(ages_after, [3, 8, 0, 8]);
+    }
+}
@@ -0,0 +1,251 @@
+use rand::distributions::{Distribution, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+/// Chooses, for every slot of `selected`, the index of an individual to be
+/// used as a parent. Lower fitnesses are better.
+pub trait Selection {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>;
+}
+
+pub struct Tournament {
+    size: usize,
+}
+impl Tournament {
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+}
+
+impl Selection for Tournament {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let mut indexes = (0..fitnesses.len()).collect::<Vec<_>>();
+
+        for s in selected {
+            let (j, _) = rand::util::partial_shuffle(rng, &mut indexes, self.size)
+                .iter()
+                .map(|&i| (i, fitnesses[i]))
+                .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+                .unwrap();
+
+            *s = j;
+        }
+    }
+}
+
+/// Fitness proportional selection. Since fitnesses are minimized, each
+/// individual is weighted by how much better it is than the worst one.
+pub struct RouletteWheel;
+
+impl Selection for RouletteWheel {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let cumulative = cumulative_weights(&proportional_weights(fitnesses));
+        let total = *cumulative.last().unwrap();
+
+        for s in selected {
+            let u = rng.sample::<f64, _>(&UniformClosedOpen01) * total;
+            *s = spin(&cumulative, u);
+        }
+    }
+}
+
+/// Same weights as [`RouletteWheel`], but all parents are chosen with a
+/// single spin of equally spaced pointers, which has minimal spread.
+pub struct StochasticUniversalSampling;
+
+impl Selection for StochasticUniversalSampling {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let cumulative = cumulative_weights(&proportional_weights(fitnesses));
+        let total = *cumulative.last().unwrap();
+
+        let step = total / (selected.len() as f64);
+        let start = rng.sample::<f64, _>(&UniformClosedOpen01) * step;
+
+        for (k, s) in selected.iter_mut().enumerate() {
+            *s = spin(&cumulative, start + (k as f64) * step);
+        }
+
+        // the pointers are sorted, so the parents would otherwise be paired
+        // with their neighbours in the population
+        rand::util::shuffle(rng, selected);
+    }
+}
+
+/// Linear ranking selection, `pressure` is the expected number of copies of
+/// the best individual and must be in `[1, 2]`.
+pub struct Rank {
+    pressure: f64,
+}
+impl Rank {
+    pub fn new(pressure: f64) -> Self {
+        Self { pressure }
+    }
+}
+
+impl Selection for Rank {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let n = fitnesses.len();
+
+        // worst first, so that the position is the rank
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| fitnesses[b].partial_cmp(&fitnesses[a]).unwrap());
+
+        let mut weights = vec![0.0; n];
+        for (rank, &i) in order.iter().enumerate() {
+            weights[i] = if n > 1 {
+                (2.0 - self.pressure) / (n as f64)
+                    + 2.0 * (rank as f64) * (self.pressure - 1.0) / ((n * (n - 1)) as f64)
+            } else {
+                1.0
+            };
+        }
+
+        let cumulative = cumulative_weights(&weights);
+        let total = *cumulative.last().unwrap();
+
+        for s in selected {
+            let u = rng.sample::<f64, _>(&UniformClosedOpen01) * total;
+            *s = spin(&cumulative, u);
+        }
+    }
+}
+
+/// Chooses parents uniformly among the best `proportion` of the population.
+pub struct Truncation {
+    proportion: f64,
+}
+impl Truncation {
+    pub fn new(proportion: f64) -> Self {
+        Self { proportion }
+    }
+}
+
+impl Selection for Truncation {
+    fn select<R>(&self, rng: &mut R, fitnesses: &[f64], selected: &mut [usize])
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let n = fitnesses.len();
+
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| fitnesses[a].partial_cmp(&fitnesses[b]).unwrap());
+
+        let k = ((self.proportion * (n as f64)).ceil() as usize).clamp(1, n);
+
+        for s in selected {
+            let j = rng.sample(&UniformClosedOpen::new(0 as f64, k as f64)) as usize;
+            *s = order[j];
+        }
+    }
+}
+
+fn proportional_weights(fitnesses: &[f64]) -> Vec<f64> {
+    let worst = fitnesses
+        .iter()
+        .copied()
+        .max_by(|a, b| a.partial_cmp(b).unwrap())
+        .unwrap();
+
+    let weights = fitnesses.iter().map(|&f| worst - f).collect::<Vec<_>>();
+
+    // all individuals are equally fit
+    if weights.iter().all(|&w| w <= 0.0) {
+        return vec![1.0; fitnesses.len()];
+    }
+
+    weights
+}
+
+fn cumulative_weights(weights: &[f64]) -> Vec<f64> {
+    weights
+        .iter()
+        .scan(0.0, |acc, &w| {
+            *acc += w;
+            Some(*acc)
+        })
+        .collect()
+}
+
+fn spin(cumulative: &[f64], u: f64) -> usize {
+    usize::min(
+        cumulative.partition_point(|&c| c <= u),
+        cumulative.len() - 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    // from the worst to the best
+    const FITNESSES: [f64; 5] = [4.0, 3.0, 2.0, 1.0, 0.0];
+
+    // the fraction of the parents chosen from every individual
+    fn frequencies<S: Selection>(selection: &S, parents: usize) -> Vec<f64> {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut counts = vec![0; FITNESSES.len()];
+
+        let mut selected = vec![0; 10];
+        for _ in 0..parents / selected.len() {
+            selection.select(&mut rng, &FITNESSES, &mut selected);
+            for &i in &selected {
+                counts[i] += 1;
+            }
+        }
+
+        counts.iter().map(|&c| c as f64 / parents as f64).collect()
+    }
+
+    #[test]
+    fn selections_work() {
+        // all of them pick the individual of rank r, counted from the worst,
+        // with probability r / 10
+        let expected = [0.0, 0.1, 0.2, 0.3, 0.4];
+
+        for frequencies in [
+            frequencies(&Tournament::new(2), 20000),
+            frequencies(&RouletteWheel, 20000),
+            frequencies(&Rank::new(2.0), 20000),
+        ] {
+            for (f, e) in frequencies.iter().zip(expected) {
+                assert!((f - e).abs() < 0.01, "{frequencies:?}");
+            }
+        }
+
+        // with ten pointers, every individual gets exactly its share
+        assert_eq!(frequencies(&StochasticUniversalSampling, 20000), expected);
+
+        let frequencies = frequencies(&Truncation::new(0.4), 20000);
+        assert_eq!(frequencies[..3], [0.0; 3]);
+        assert!((frequencies[3] - 0.5).abs() < 0.01);
+
+        // without pressure, ranking is uniform
+        let mut rng = Xoshiro256Plus::new([5, 6, 7, 8]);
+        let mut selected = vec![0; 20000];
+        Rank::new(1.0).select(&mut rng, &FITNESSES, &mut selected);
+        for i in 0..FITNESSES.len() {
+            let count = selected.iter().filter(|&&s| s == i).count();
+            assert!((count as f64 - 4000.0).abs() < 200.0);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+/// What a cooling schedule may look at when lowering the temperature.
+pub struct Annealing {
+    pub initial_temperature: f64,
+    pub temperature: f64,
+    pub iterations: usize,
+    /// Fraction of the budget already used, from 0 to 1.
+    pub progress: f64,
+    /// Moving average of the fraction of accepted moves.
+    pub acceptance_rate: f64,
+}
+
+pub trait Cooling {
+    /// Returns the temperature for the next iteration.
+    fn cool(&self, annealing: &Annealing) -> f64;
+}
+
+/// `T ← αT`
+pub struct Geometric {
+    alpha: f64,
+}
+impl Geometric {
+    pub fn new(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Cooling for Geometric {
+    fn cool(&self, annealing: &Annealing) -> f64 {
+        self.alpha * annealing.temperature
+    }
+}
+
+/// Decreases the temperature linearly, reaching zero when the budget ends.
+pub struct Linear;
+
+impl Cooling for Linear {
+    fn cool(&self, annealing: &Annealing) -> f64 {
+        annealing.initial_temperature * (1.0 - annealing.progress)
+    }
+}
+
+/// `T = T₀ / ln(e + k)`, the schedule with convergence guarantees, which is
+/// also very slow.
+pub struct Logarithmic;
+
+impl Cooling for Logarithmic {
+    fn cool(&self, annealing: &Annealing) -> f64 {
+        annealing.initial_temperature
+            / f64::ln(std::f64::consts::E + ((annealing.iterations + 1) as f64))
+    }
+}
+
+/// `T ← T / (1 + βT)`
+pub struct LundyMees {
+    beta: f64,
+}
+impl LundyMees {
+    pub fn new(beta: f64) -> Self {
+        Self { beta }
+    }
+}
+
+impl Cooling for LundyMees {
+    fn cool(&self, annealing: &Annealing) -> f64 {
+        annealing.temperature / (1.0 + self.beta * annealing.temperature)
+    }
+}
+
+/// Steers the temperature so that the acceptance rate follows a target that
+/// decreases linearly from `initial_acceptance` to zero over the run. The
+/// temperature goes down when too many moves are accepted and up when too
+/// few are, by a factor controlled by `gain`.
+pub struct Adaptive {
+    initial_acceptance: f64,
+    gain: f64,
+}
+impl Adaptive {
+    pub fn new(initial_acceptance: f64, gain: f64) -> Self {
+        Self {
+            initial_acceptance,
+            gain,
+        }
+    }
+}
+
+impl Cooling for Adaptive {
+    fn cool(&self, annealing: &Annealing) -> f64 {
+        let target = self.initial_acceptance * (1.0 - annealing.progress);
+
+        annealing.temperature * f64::exp(self.gain * (target - annealing.acceptance_rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_work() {
+        let annealing = Annealing {
+            initial_temperature: 100.0,
+            temperature: 50.0,
+            iterations: 9,
+            progress: 0.25,
+            acceptance_rate: 0.5,
+        };
+
+        assert_eq!(Geometric::new(0.9).cool(&annealing), 45.0);
+        assert_eq!(Linear.cool(&annealing), 75.0);
+        assert_eq!(
+            Logarithmic.cool(&annealing),
+            100.0 / f64::ln(std::f64::consts::E + 10.0)
+        );
+        assert_eq!(LundyMees::new(0.01).cool(&annealing), 50.0 / 1.5);
+
+        // the target rate is 0.6, so too few moves are accepted and the
+        // temperature goes up
+        let adaptive = Adaptive::new(0.8, 2.0);
+        assert!((adaptive.cool(&annealing) - 50.0 * f64::exp(0.2)).abs() < 1e-12);
+
+        let annealing = Annealing {
+            acceptance_rate: 0.9,
+            ..annealing
+        };
+        assert!(adaptive.cool(&annealing) < 50.0);
+
+        // linear cooling ends at zero
+        let annealing = Annealing {
+            progress: 1.0,
+            ..annealing
+        };
+        assert_eq!(Linear.cool(&annealing), 0.0);
+    }
+}
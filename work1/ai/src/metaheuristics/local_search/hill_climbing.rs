@@ -0,0 +1,93 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{LocalSearch, Neighborhood};
+use crate::metaheuristics::{Problem, Solution, Termination};
+
+/// Stochastic hill climbing, moves to a random neighbour whenever it is better
+/// and gives up after `patience` consecutive failures.
+pub struct HillClimbing {
+    neighborhood: Neighborhood,
+    patience: usize,
+}
+impl HillClimbing {
+    pub fn new(neighborhood: Neighborhood, patience: usize) -> Self {
+        Self {
+            neighborhood,
+            patience,
+        }
+    }
+}
+
+impl LocalSearch for HillClimbing {
+    fn refine<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        x: [f64; D],
+        fitness: f64,
+        termination: Termination,
+    ) -> Solution<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut iterations = 0;
+        let mut evaluations = 0;
+
+        let (mut x, mut fitness) = (x, fitness);
+        let mut failures = 0;
+
+        while failures < self.patience && !termination.is_met(iterations, evaluations, fitness) {
+            let y = self.neighborhood.neighbor(rng, &x, &P::RANGES);
+            let y_fitness = P::phi(&y);
+            evaluations += 1;
+
+            if y_fitness < fitness {
+                (x, fitness) = (y, y_fitness);
+                failures = 0;
+            } else {
+                failures += 1;
+            }
+
+            iterations += 1;
+        }
+
+        Solution {
+            x,
+            fitness,
+            iterations,
+            evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn hill_climbing_works() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let search = HillClimbing::new(Neighborhood::Gaussian { sigma: 0.01 }, 50);
+
+        let x = [1.0, -1.0, 0.5, 0.0];
+        let solution = search.refine::<_, Sphere, 4>(
+            &mut rng,
+            x,
+            Sphere::phi(&x),
+            Termination::evaluations(5000),
+        );
+        assert!(solution.fitness < 1e-2, "{solution:?}");
+        assert_eq!(solution.fitness, Sphere::phi(&solution.x));
+
+        // nothing beats the optimum, so it gives up once out of patience
+        let solution =
+            search.refine::<_, Sphere, 4>(&mut rng, [0.0; 4], 0.0, Termination::evaluations(5000));
+        assert_eq!(solution.x, [0.0; 4]);
+        assert_eq!((solution.iterations, solution.evaluations), (50, 50));
+    }
+}
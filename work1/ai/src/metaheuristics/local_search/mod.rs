@@ -0,0 +1,398 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::observer::Observer;
+use super::{run, Metaheuristic, Population, Problem, Range, Solution, Termination};
+
+mod cooling;
+mod hill_climbing;
+mod nelder_mead;
+mod pattern_search;
+
+pub use cooling::{Adaptive, Annealing, Cooling, Geometric, Linear, Logarithmic, LundyMees};
+pub use hill_climbing::HillClimbing;
+pub use nelder_mead::NelderMead;
+pub use pattern_search::{CompassSearch, HookeJeeves};
+
+/// A method that improves on a given starting point.
+pub trait LocalSearch {
+    /// Refines `x`, whose `phi` value is `fitness`, until `termination` is met
+    /// or the method converges. The evaluation of `x` itself is not counted.
+    fn refine<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        x: [f64; D],
+        fitness: f64,
+        termination: Termination,
+    ) -> Solution<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>;
+}
+
+/// Runs a global optimizer and polishes the best point it finds with `local`,
+/// which is given its own `termination`. The costs of both are added up.
+pub fn hybrid<R, P, G, L, const D: usize>(
+    rng: &mut R,
+    global: G,
+    local: &L,
+    termination: Termination,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    G: FnOnce(&mut R) -> Solution<D>,
+    L: LocalSearch,
+{
+    let global = global(rng);
+    let local = local.refine::<R, P, D>(rng, global.x, global.fitness, termination);
+
+    let (x, fitness) = if local.fitness < global.fitness {
+        (local.x, local.fitness)
+    } else {
+        (global.x, global.fitness)
+    };
+
+    Solution {
+        x,
+        fitness,
+        iterations: global.iterations + local.iterations,
+        evaluations: global.evaluations + local.evaluations,
+    }
+}
+
+fn project<const D: usize>(x: &mut [f64; D], ranges: &[Range<f64>; D]) {
+    for (xi, range) in x.iter_mut().zip(ranges) {
+        *xi = xi.clamp(range.start, range.end);
+    }
+}
+
+/// How a neighbour of the current point is generated. Step sizes are relative
+/// to the width of the range of each coordinate, and the neighbour is clamped
+/// back into the ranges.
+#[derive(Clone, Copy)]
+pub enum Neighborhood {
+    Gaussian { sigma: f64 },
+    Uniform { radius: f64 },
+}
+
+impl Neighborhood {
+    fn neighbor<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        x: &[f64; D],
+        ranges: &[Range<f64>; D],
+    ) -> [f64; D]
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    {
+        let mut y = *x;
+
+        for (yi, range) in y.iter_mut().zip(ranges) {
+            let width = range.end - range.start;
+
+            let step = match *self {
+                Self::Gaussian { sigma } => rng.sample(&Normal::new(0.0, sigma * width)),
+                Self::Uniform { radius } => {
+                    rng.sample(&UniformClosedOpen::new(-radius * width, radius * width))
+                }
+            };
+
+            *yi = (*yi + step).clamp(range.start, range.end);
+        }
+
+        y
+    }
+}
+
+/// Resets the temperature to `fraction` of the initial one after `stall`
+/// iterations without improving the best point.
+#[derive(Clone, Copy)]
+pub struct Reheating {
+    pub stall: usize,
+    pub fraction: f64,
+}
+
+pub struct SimulatedAnnealing<C> {
+    pub neighborhood: Neighborhood,
+    pub initial_temperature: f64,
+    pub cooling: C,
+    pub reheating: Option<Reheating>,
+}
+
+/// Simulated annealing, run as a population of the single current point. Its
+/// `parameters` are the temperature, the acceptance rate and the number of
+/// iterations since the best point last improved.
+pub fn simulated_annealing<R, P, C, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &SimulatedAnnealing<C>,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    C: Cooling,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl<C> Metaheuristic for SimulatedAnnealing<C>
+where
+    C: Cooling,
+{
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Population::random::<R, P>(rng, 1, 1);
+        population.parameters = vec![self.initial_temperature, 1.0, 0.0];
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let SimulatedAnnealing {
+            neighborhood,
+            initial_temperature,
+            ref cooling,
+            reheating,
+        } = *self;
+
+        let [temperature, acceptance_rate, stall] = population.parameters[..] else {
+            panic!("simulated annealing keeps three parameters");
+        };
+        let mut stall = stall as usize;
+
+        let current = population.individuals[0];
+        let current_fitness = population.fitnesses[0];
+
+        let candidate = neighborhood.neighbor(rng, &current, &P::RANGES);
+        let candidate_fitness = P::phi(&candidate);
+        population.evaluations += 1;
+
+        // metropolis criterion
+        let delta = candidate_fitness - current_fitness;
+        let accepted = delta <= 0.0
+            || rng.sample::<f64, _>(&UniformClosedOpen01) < f64::exp(-delta / temperature);
+
+        if accepted {
+            population.individuals[0] = candidate;
+            population.fitnesses[0] = candidate_fitness;
+        }
+
+        let acceptance_rate = 0.99 * acceptance_rate + 0.01 * (accepted as u8 as f64);
+
+        if population.fitnesses[0] < population.best_fitness {
+            population.best_individual = population.individuals[0];
+            population.best_fitness = population.fitnesses[0];
+            stall = 0;
+        } else {
+            stall += 1;
+        }
+
+        population.iterations += 1;
+
+        let mut temperature = cooling.cool(&Annealing {
+            initial_temperature,
+            temperature,
+            iterations: population.iterations,
+            progress: termination.progress(population.iterations, population.evaluations),
+            acceptance_rate,
+        });
+
+        if let Some(reheating) = reheating {
+            if stall >= reheating.stall {
+                temperature = reheating.fraction * initial_temperature;
+                stall = 0;
+            }
+        }
+
+        population.parameters = vec![temperature, acceptance_rate, stall as f64];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use super::*;
+    use crate::metaheuristics::checkpoint;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    // the current fitness and the parameters after every iteration
+    #[derive(Default)]
+    struct Trace {
+        fitnesses: Vec<f64>,
+        parameters: Vec<Vec<f64>>,
+    }
+
+    impl<const D: usize> Observer<D> for Trace {
+        fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+            self.fitnesses.push(population.fitnesses[0]);
+            self.parameters.push(population.parameters.clone());
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn annealing<C: Cooling>(
+        initial_temperature: f64,
+        cooling: C,
+        reheating: Option<Reheating>,
+    ) -> (Solution<4>, Trace) {
+        let mut trace = Trace::default();
+        let solution = simulated_annealing::<_, Sphere, _, _, 4>(
+            &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+            Termination::iterations(2000),
+            &SimulatedAnnealing {
+                neighborhood: Neighborhood::Gaussian { sigma: 0.05 },
+                initial_temperature,
+                cooling,
+                reheating,
+            },
+            &mut trace,
+        );
+        (solution, trace)
+    }
+
+    #[test]
+    fn simulated_annealing_works() {
+        // near zero, the metropolis criterion only accepts improvements
+        let (solution, trace) = annealing(1e-300, Geometric::new(0.99), None);
+        assert!(trace.fitnesses.windows(2).all(|w| w[1] <= w[0]));
+        assert_eq!(solution.fitness, *trace.fitnesses.last().unwrap());
+        assert!(solution.fitness < 0.1);
+        assert_eq!(solution.evaluations, 2001);
+
+        // while a hot run accepts almost every move
+        let (_, trace) = annealing(1e300, Geometric::new(1.0), None);
+        let [_, acceptance_rate, _] = trace.parameters.last().unwrap()[..] else {
+            panic!()
+        };
+        assert!(acceptance_rate > 0.99);
+    }
+
+    #[test]
+    fn reheating_works() {
+        let reheating = Reheating {
+            stall: 50,
+            fraction: 0.5,
+        };
+        let (_, trace) = annealing(1.0, Geometric::new(0.9), Some(reheating));
+
+        // the temperature is only ever reset when the stall counter is
+        let reheated = trace
+            .parameters
+            .iter()
+            .filter(|p| p[0] == 0.5)
+            .collect::<Vec<_>>();
+        assert!(!reheated.is_empty());
+        assert!(reheated.iter().all(|p| p[2] == 0.0));
+        assert!(trace.parameters.iter().all(|p| p[2] < 50.0));
+    }
+
+    #[test]
+    fn hybrid_works() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let termination = Termination::evaluations(1000);
+        let parameters = SimulatedAnnealing {
+            neighborhood: Neighborhood::Gaussian { sigma: 0.1 },
+            initial_temperature: 1.0,
+            cooling: Geometric::new(0.95),
+            reheating: None,
+        };
+
+        let mut global = None;
+        let solution = hybrid::<_, Sphere, _, _, 4>(
+            &mut rng,
+            |rng| {
+                let solution = simulated_annealing::<_, Sphere, _, _, 4>(
+                    rng,
+                    Termination::iterations(100),
+                    &parameters,
+                    &mut (),
+                );
+                global = Some(solution);
+                solution
+            },
+            &NelderMead::new(0.05, true, 1e-12),
+            termination,
+        );
+        let global = global.unwrap();
+
+        // the local search polishes the point found by the global one, and
+        // the cost of both is counted
+        assert!(solution.fitness < 1e-10 && global.fitness > 1e-3);
+        assert_eq!(global.iterations, 100);
+        assert!(solution.iterations > global.iterations);
+        assert!(solution.evaluations > global.evaluations);
+        assert!(solution.evaluations - global.evaluations <= 1000 + 4);
+    }
+
+    #[test]
+    fn checkpoints_keep_the_temperature() {
+        let parameters = SimulatedAnnealing {
+            neighborhood: Neighborhood::Uniform { radius: 0.1 },
+            initial_temperature: 10.0,
+            cooling: Adaptive::new(0.5, 1.0),
+            reheating: Some(Reheating {
+                stall: 20,
+                fraction: 0.3,
+            }),
+        };
+        let termination = Termination::iterations(300);
+
+        let mut rng = Xoshiro256Plus::new([5, 6, 7, 8]);
+        let uninterrupted =
+            simulated_annealing::<_, Sphere, _, _, 4>(&mut rng, termination, &parameters, &mut ());
+
+        let mut rng = Xoshiro256Plus::new([5, 6, 7, 8]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        for _ in 0..100 {
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+        }
+
+        let mut file = Vec::new();
+        checkpoint::write(&mut file, &rng, &population).unwrap();
+        let (mut rng, resumed) = checkpoint::read::<Xoshiro256Plus, _, 4>(&file[..]).unwrap();
+
+        assert_eq!(
+            resumed
+                .parameters
+                .iter()
+                .map(|p| p.to_bits())
+                .collect::<Vec<_>>(),
+            population
+                .parameters
+                .iter()
+                .map(|p| p.to_bits())
+                .collect::<Vec<_>>()
+        );
+
+        let resumed =
+            run::<_, Sphere, _, _, 4>(&mut rng, termination, &parameters, resumed, &mut ());
+        assert_eq!(resumed, uninterrupted);
+    }
+}
@@ -0,0 +1,221 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{project, LocalSearch};
+use crate::metaheuristics::{Problem, Solution, Termination};
+
+/// Nelder-Mead downhill simplex, with trial points projected into the ranges.
+///
+/// The initial simplex has edges of `initial_step` times the width of each
+/// range. With `adaptive`, the coefficients depend on the dimension (Gao and
+/// Han), which behaves much better than the standard ones when `D` is large.
+/// The search stops early once both the spread of the values and the size of
+/// the simplex, in range widths, fall below `tolerance`.
+pub struct NelderMead {
+    initial_step: f64,
+    adaptive: bool,
+    tolerance: f64,
+}
+impl NelderMead {
+    pub fn new(initial_step: f64, adaptive: bool, tolerance: f64) -> Self {
+        Self {
+            initial_step,
+            adaptive,
+            tolerance,
+        }
+    }
+}
+
+impl NelderMead {
+    // reflection, expansion, contraction and shrink in `n` dimensions
+    fn coefficients(&self, n: f64) -> (f64, f64, f64, f64) {
+        if self.adaptive {
+            (1.0, 1.0 + 2.0 / n, 0.75 - 1.0 / (2.0 * n), 1.0 - 1.0 / n)
+        } else {
+            (1.0, 2.0, 0.5, 0.5)
+        }
+    }
+}
+
+impl LocalSearch for NelderMead {
+    fn refine<R, P, const D: usize>(
+        &self,
+        _rng: &mut R,
+        x: [f64; D],
+        fitness: f64,
+        termination: Termination,
+    ) -> Solution<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = D as f64;
+        let (reflection, expansion, contraction, shrink) = self.coefficients(n);
+
+        let mut iterations = 0;
+        let mut evaluations = 0;
+
+        let mut simplex = vec![(x, fitness)];
+        for i in 0..D {
+            let range = P::RANGES[i];
+            let step = self.initial_step * (range.end - range.start);
+
+            let mut vertex = x;
+            vertex[i] = if vertex[i] + step <= range.end {
+                vertex[i] + step
+            } else {
+                vertex[i] - step
+            };
+            project(&mut vertex, &P::RANGES);
+
+            simplex.push((vertex, P::phi(&vertex)));
+            evaluations += 1;
+        }
+
+        simplex.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+
+        while !termination.is_met(iterations, evaluations, simplex[0].1) {
+            let (best, best_fitness) = simplex[0];
+            let worst_fitness = simplex[D].1;
+
+            let size = simplex[1..]
+                .iter()
+                .flat_map(|(vertex, _)| {
+                    vertex
+                        .iter()
+                        .zip(&best)
+                        .zip(&P::RANGES)
+                        .map(|((a, b), range)| f64::abs(a - b) / (range.end - range.start))
+                })
+                .fold(0.0, f64::max);
+
+            if worst_fitness - best_fitness <= self.tolerance && size <= self.tolerance {
+                break;
+            }
+
+            // centroid of all vertices but the worst
+            let mut centroid = [0.0; D];
+            for (vertex, _) in &simplex[..D] {
+                for (c, v) in centroid.iter_mut().zip(vertex) {
+                    *c += v / n;
+                }
+            }
+
+            let towards = |coefficient: f64| {
+                let mut point = [0.0; D];
+                for ((p, c), w) in point.iter_mut().zip(&centroid).zip(&simplex[D].0) {
+                    *p = c + coefficient * (c - w);
+                }
+                project(&mut point, &P::RANGES);
+                point
+            };
+
+            let reflected = towards(reflection);
+            let reflected_fitness = P::phi(&reflected);
+            evaluations += 1;
+
+            if reflected_fitness < best_fitness {
+                let expanded = towards(reflection * expansion);
+                let expanded_fitness = P::phi(&expanded);
+                evaluations += 1;
+
+                simplex[D] = if expanded_fitness < reflected_fitness {
+                    (expanded, expanded_fitness)
+                } else {
+                    (reflected, reflected_fitness)
+                };
+            } else if reflected_fitness < simplex[D - 1].1 {
+                simplex[D] = (reflected, reflected_fitness);
+            } else {
+                let (contracted, contracted_fitness, threshold) =
+                    if reflected_fitness < worst_fitness {
+                        // outside contraction
+                        let point = towards(reflection * contraction);
+                        let fitness = P::phi(&point);
+                        (point, fitness, reflected_fitness)
+                    } else {
+                        // inside contraction
+                        let point = towards(-contraction);
+                        let fitness = P::phi(&point);
+                        (point, fitness, worst_fitness)
+                    };
+                evaluations += 1;
+
+                if contracted_fitness < threshold {
+                    simplex[D] = (contracted, contracted_fitness);
+                } else {
+                    for (vertex, fitness) in &mut simplex[1..] {
+                        for (v, b) in vertex.iter_mut().zip(&best) {
+                            *v = b + shrink * (*v - b);
+                        }
+                        *fitness = P::phi(vertex);
+                        evaluations += 1;
+                    }
+                }
+            }
+
+            simplex.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+
+            iterations += 1;
+        }
+
+        let (x, fitness) = simplex[0];
+
+        Solution {
+            x,
+            fitness,
+            iterations,
+            evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn coefficients_work() {
+        let standard = (1.0, 2.0, 0.5, 0.5);
+        assert_eq!(
+            NelderMead::new(0.1, false, 0.0).coefficients(10.0),
+            standard
+        );
+
+        // Gao and Han's coefficients only differ from the standard ones above
+        // two dimensions
+        let adaptive = NelderMead::new(0.1, true, 0.0);
+        assert_eq!(adaptive.coefficients(2.0), standard);
+        assert_eq!(adaptive.coefficients(4.0), (1.0, 1.5, 0.625, 0.75));
+        assert_eq!(adaptive.coefficients(10.0), (1.0, 1.2, 0.7, 0.9));
+    }
+
+    #[test]
+    fn nelder_mead_works() {
+        let x = [4.0, -3.0, 2.0, 1.0];
+        for adaptive in [false, true] {
+            let solution = NelderMead::new(0.1, adaptive, 1e-10).refine::<_, Sphere, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                x,
+                Sphere::phi(&x),
+                Termination::evaluations(2000),
+            );
+            assert!(solution.fitness < 1e-8, "{solution:?}");
+            assert!(solution.evaluations <= 2000 + 4);
+        }
+
+        // trial points outside the ranges are projected into them
+        let corner = [5.0; 4];
+        let solution = NelderMead::new(0.5, true, 1e-10).refine::<_, Sphere, 4>(
+            &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+            corner,
+            Sphere::phi(&corner),
+            Termination::iterations(20),
+        );
+        assert!(solution.x.iter().all(|x| (-5.0..=5.0).contains(x)));
+    }
+}
@@ -0,0 +1,247 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{project, LocalSearch};
+use crate::metaheuristics::{Problem, Range, Solution, Termination};
+
+/// Hooke-Jeeves pattern search. Steps are relative to the width of each range,
+/// start at `initial_step`, are multiplied by `shrink` whenever no exploratory
+/// move improves, and the search stops once they fall below `tolerance`.
+pub struct HookeJeeves {
+    initial_step: f64,
+    shrink: f64,
+    tolerance: f64,
+}
+impl HookeJeeves {
+    pub fn new(initial_step: f64, shrink: f64, tolerance: f64) -> Self {
+        Self {
+            initial_step,
+            shrink,
+            tolerance,
+        }
+    }
+}
+
+impl LocalSearch for HookeJeeves {
+    fn refine<R, P, const D: usize>(
+        &self,
+        _rng: &mut R,
+        x: [f64; D],
+        fitness: f64,
+        termination: Termination,
+    ) -> Solution<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut iterations = 0;
+        let mut evaluations = 0;
+
+        let (mut base, mut base_fitness) = (x, fitness);
+        let mut step = self.initial_step;
+
+        while step >= self.tolerance && !termination.is_met(iterations, evaluations, base_fitness) {
+            let (explored, explored_fitness) =
+                explore::<P, D>(base, base_fitness, step, &P::RANGES, &mut evaluations);
+
+            if explored_fitness < base_fitness {
+                // keep moving along the pattern while it pays off
+                let (mut previous, mut current, mut current_fitness) =
+                    (base, explored, explored_fitness);
+
+                loop {
+                    let mut pattern = [0.0; D];
+                    for ((p, c), b) in pattern.iter_mut().zip(&current).zip(&previous) {
+                        *p = 2.0 * c - b;
+                    }
+                    project(&mut pattern, &P::RANGES);
+                    let pattern_fitness = P::phi(&pattern);
+                    evaluations += 1;
+
+                    let (next, next_fitness) = explore::<P, D>(
+                        pattern,
+                        pattern_fitness,
+                        step,
+                        &P::RANGES,
+                        &mut evaluations,
+                    );
+
+                    if next_fitness >= current_fitness
+                        || termination.is_met(iterations, evaluations, next_fitness)
+                    {
+                        if next_fitness < current_fitness {
+                            (current, current_fitness) = (next, next_fitness);
+                        }
+                        break;
+                    }
+
+                    (previous, current, current_fitness) = (current, next, next_fitness);
+                }
+
+                (base, base_fitness) = (current, current_fitness);
+            } else {
+                step *= self.shrink;
+            }
+
+            iterations += 1;
+        }
+
+        Solution {
+            x: base,
+            fitness: base_fitness,
+            iterations,
+            evaluations,
+        }
+    }
+}
+
+// tries to improve each coordinate in turn by a step in either direction
+fn explore<P, const D: usize>(
+    mut x: [f64; D],
+    mut fitness: f64,
+    step: f64,
+    ranges: &[Range<f64>; D],
+    evaluations: &mut usize,
+) -> ([f64; D], f64)
+where
+    P: Problem<D>,
+{
+    for i in 0..D {
+        let delta = step * (ranges[i].end - ranges[i].start);
+
+        for direction in [1.0, -1.0] {
+            let mut y = x;
+            y[i] = (y[i] + direction * delta).clamp(ranges[i].start, ranges[i].end);
+
+            let y_fitness = P::phi(&y);
+            *evaluations += 1;
+
+            if y_fitness < fitness {
+                (x, fitness) = (y, y_fitness);
+                break;
+            }
+        }
+    }
+
+    (x, fitness)
+}
+
+/// Compass (coordinate) search, polls the `2D` coordinate directions and moves
+/// to the first one that improves. Steps behave as in [`HookeJeeves`].
+pub struct CompassSearch {
+    initial_step: f64,
+    shrink: f64,
+    tolerance: f64,
+}
+impl CompassSearch {
+    pub fn new(initial_step: f64, shrink: f64, tolerance: f64) -> Self {
+        Self {
+            initial_step,
+            shrink,
+            tolerance,
+        }
+    }
+}
+
+impl LocalSearch for CompassSearch {
+    fn refine<R, P, const D: usize>(
+        &self,
+        _rng: &mut R,
+        x: [f64; D],
+        fitness: f64,
+        termination: Termination,
+    ) -> Solution<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut iterations = 0;
+        let mut evaluations = 0;
+
+        let (mut x, mut fitness) = (x, fitness);
+        let mut step = self.initial_step;
+
+        'search: while step >= self.tolerance
+            && !termination.is_met(iterations, evaluations, fitness)
+        {
+            iterations += 1;
+
+            for i in 0..D {
+                let range = P::RANGES[i];
+                let delta = step * (range.end - range.start);
+
+                for direction in [1.0, -1.0] {
+                    let mut y = x;
+                    y[i] = (y[i] + direction * delta).clamp(range.start, range.end);
+
+                    let y_fitness = P::phi(&y);
+                    evaluations += 1;
+
+                    if y_fitness < fitness {
+                        (x, fitness) = (y, y_fitness);
+                        continue 'search;
+                    }
+                }
+            }
+
+            step *= self.shrink;
+        }
+
+        Solution {
+            x,
+            fitness,
+            iterations,
+            evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn refine<L: LocalSearch>(search: L, x: [f64; 4]) -> Solution<4> {
+        search.refine::<_, Sphere, 4>(
+            &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+            x,
+            Sphere::phi(&x),
+            Termination::evaluations(5000),
+        )
+    }
+
+    #[test]
+    fn pattern_searches_work() {
+        let x = [4.0, -3.0, 2.1, 1.3];
+        let solution = refine(HookeJeeves::new(0.1, 0.5, 1e-8), x);
+        assert!(solution.fitness < 1e-12, "{solution:?}");
+        let solution = refine(CompassSearch::new(0.1, 0.5, 1e-8), x);
+        assert!(solution.fitness < 1e-12, "{solution:?}");
+    }
+
+    #[test]
+    fn steps_shrink_on_failure() {
+        // every poll from the optimum fails, so each iteration halves the step,
+        // from 0.1 until it falls below the tolerance after 7 of them, with
+        // both directions of the 4 coordinates evaluated every time
+        let optimum = [0.0; 4];
+        for solution in [
+            refine(HookeJeeves::new(0.1, 0.5, 1e-3), optimum),
+            refine(CompassSearch::new(0.1, 0.5, 1e-3), optimum),
+        ] {
+            assert_eq!(solution.x, optimum);
+            assert_eq!(solution.iterations, 7);
+            assert_eq!(solution.evaluations, 7 * 2 * 4);
+        }
+
+        // a step of 0.1 is a move of 1, which overshoots 0.3 while 0.5 does not
+        let solution = refine(CompassSearch::new(0.1, 0.5, 0.04), [0.3, 0.0, 0.0, 0.0]);
+        assert_eq!(solution.x, [-0.2, 0.0, 0.0, 0.0]);
+        assert_eq!(solution.evaluations, 2 * 4 + 2 + 2 * 4);
+    }
+}
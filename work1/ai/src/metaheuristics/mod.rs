@@ -1,7 +1,16 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use observer::Observer;
+
+pub mod checkpoint;
+pub mod combinatorial;
 pub mod evolutionary_computation;
+pub mod local_search;
+pub mod observer;
 pub mod swarm_intelligence;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Range<T> {
     pub start: T,
     pub end: T,
@@ -23,30 +32,307 @@ pub trait Problem<const D: usize> {
     const PENALTY_WEIGHT: f64;
 
     fn phi(x: &[f64; D]) -> f64 {
-        let mut y = Self::f(x);
+        Self::f(x) + Self::PENALTY_WEIGHT * Self::violation(x)
+    }
+
+    /// Total amount by which `x` leaves the ranges and breaks the inequalities,
+    /// zero when it is feasible.
+    fn violation(x: &[f64; D]) -> f64 {
+        let mut violation = 0.0;
 
         for (range, &xi) in Self::RANGES.iter().zip(x) {
-            let d = range.start - xi;
-            if d > 0.0 {
-                y += Self::PENALTY_WEIGHT * d;
+            violation += f64::max(range.start - xi, 0.0) + f64::max(xi - range.end, 0.0);
+        }
 
-                // only one of the conditions can happen, so we can continue early
-                continue;
-            }
+        for inequality in Self::INEQUALITIES {
+            violation += f64::max(inequality(x), 0.0);
+        }
+
+        violation
+    }
+}
+
+/// When to stop a run, whichever limit is reached first ends it.
+#[derive(Clone, Copy, Debug)]
+pub struct Termination {
+    pub max_iterations: usize,
+    pub max_evaluations: usize,
+    /// The run also stops once a `phi` value at or below this is found.
+    pub target: f64,
+}
+
+impl Termination {
+    pub const fn iterations(max_iterations: usize) -> Self {
+        Self {
+            max_iterations,
+            max_evaluations: usize::MAX,
+            target: f64::NEG_INFINITY,
+        }
+    }
+
+    pub const fn evaluations(max_evaluations: usize) -> Self {
+        Self {
+            max_iterations: usize::MAX,
+            max_evaluations,
+            target: f64::NEG_INFINITY,
+        }
+    }
 
-            let d = xi - range.end;
-            if d > 0.0 {
-                y += Self::PENALTY_WEIGHT * d;
+    pub const fn with_target(self, target: f64) -> Self {
+        Self { target, ..self }
+    }
+
+    pub fn is_met(&self, iterations: usize, evaluations: usize, best_fitness: f64) -> bool {
+        iterations >= self.max_iterations
+            || evaluations >= self.max_evaluations
+            || best_fitness <= self.target
+    }
+
+    /// Fraction of the budget already used, from 0 to 1.
+    pub fn progress(&self, iterations: usize, evaluations: usize) -> f64 {
+        f64::max(
+            (iterations as f64) / (self.max_iterations as f64),
+            (evaluations as f64) / (self.max_evaluations as f64),
+        )
+        .min(1.0)
+    }
+}
+
+/// Best point found by a run, along with the cost of finding it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Solution<const D: usize> {
+    pub x: [f64; D],
+    pub fitness: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+}
+
+/// The state of a population based run.
+#[derive(Clone)]
+pub struct Population<const D: usize> {
+    pub individuals: Vec<[f64; D]>,
+    pub fitnesses: Vec<f64>,
+    /// Number of replacement steps each individual has survived, only kept
+    /// up to date by the algorithms that need it.
+    pub ages: Vec<usize>,
+    pub best_individual: [f64; D],
+    pub best_fitness: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+    /// Adaptive state of the algorithm that is not part of the individuals,
+    /// such as the temperature of simulated annealing.
+    pub parameters: Vec<f64>,
+}
+
+impl<const D: usize> Population<D> {
+    /// Draws `size` individuals uniformly from the ranges of `P`, evaluated
+    /// by `workers` threads.
+    pub fn random<R, P>(rng: &mut R, size: usize, workers: usize) -> Self
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Self {
+            individuals: vec![[0.0; D]; size],
+            fitnesses: vec![0.0; size],
+            ages: vec![0; size],
+            best_individual: [0.0; D],
+            best_fitness: f64::INFINITY,
+            iterations: 0,
+            evaluations: 0,
+            parameters: Vec::new(),
+        };
+
+        for individual in &mut population.individuals {
+            for (x, range) in individual.iter_mut().zip(&P::RANGES) {
+                *x = rng.sample(&UniformClosedOpen::new(range.start, range.end));
             }
         }
 
-        for inequality in Self::INEQUALITIES {
-            let g = inequality(x);
-            if g > 0.0 {
-                y += Self::PENALTY_WEIGHT * g;
+        evaluate::<P, D>(&population.individuals, &mut population.fitnesses, workers);
+        population.evaluations += size;
+
+        for (individual, &fitness) in population.individuals.iter().zip(&population.fitnesses) {
+            if fitness < population.best_fitness {
+                population.best_individual = *individual;
+                population.best_fitness = fitness;
             }
         }
 
-        y
+        population
+    }
+
+    /// Records `x` as the best point so far if it improves on it.
+    pub fn update_best(&mut self, x: &[f64; D], fitness: f64) {
+        if fitness < self.best_fitness {
+            self.best_individual = *x;
+            self.best_fitness = fitness;
+        }
+    }
+
+    pub fn is_done(&self, termination: &Termination) -> bool {
+        termination.is_met(self.iterations, self.evaluations, self.best_fitness)
+    }
+
+    pub fn solution(&self) -> Solution<D> {
+        Solution {
+            x: self.best_individual,
+            fitness: self.best_fitness,
+            iterations: self.iterations,
+            evaluations: self.evaluations,
+        }
+    }
+}
+
+/// An algorithm whose whole state is kept in a [`Population`], so that it can
+/// be advanced one iteration at a time.
+pub trait Metaheuristic {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>;
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>;
+}
+
+/// Iterates `population` with `algorithm` until `termination` is met or
+/// `observer` stops the run.
+pub fn run<R, P, E, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    algorithm: &E,
+    mut population: Population<D>,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    E: Metaheuristic,
+    O: Observer<D>,
+{
+    observer.initialize(&population);
+
+    while !population.is_done(&termination) {
+        algorithm.iterate::<R, P, D>(rng, &mut population, &termination);
+
+        if observer.generation(&population).is_break() {
+            break;
+        }
+    }
+
+    observer.terminate(&population);
+
+    population.solution()
+}
+
+/// Computes `P::phi` of every individual, splitting them evenly over
+/// `workers` threads. Evaluations draw no random numbers, so the results do
+/// not depend on the number of workers.
+pub fn evaluate<P, const D: usize>(individuals: &[[f64; D]], fitnesses: &mut [f64], workers: usize)
+where
+    P: Problem<D>,
+{
+    evaluate_with(individuals, fitnesses, workers, P::phi);
+}
+
+// `evaluate` with any function of the individuals, such as `P::f`
+pub(crate) fn evaluate_with<const D: usize>(
+    individuals: &[[f64; D]],
+    fitnesses: &mut [f64],
+    workers: usize,
+    function: fn(&[f64; D]) -> f64,
+) {
+    if workers <= 1 || individuals.len() <= 1 {
+        for (individual, fitness) in individuals.iter().zip(fitnesses) {
+            *fitness = function(individual);
+        }
+        return;
+    }
+
+    let chunk_size = individuals.len().div_ceil(workers);
+
+    std::thread::scope(|scope| {
+        for (individuals, fitnesses) in individuals
+            .chunks(chunk_size)
+            .zip(fitnesses.chunks_mut(chunk_size))
+        {
+            scope.spawn(move || {
+                for (individual, fitness) in individuals.iter().zip(fitnesses) {
+                    *fitness = function(individual);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::evolutionary_computation::*;
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    pub(crate) enum Sphere {}
+
+    impl Problem<4> for Sphere {
+        fn f(x: &[f64; 4]) -> f64 {
+            x.iter().map(|x| x * x).sum()
+        }
+
+        const RANGES: [Range<f64>; 4] = [Range::new(-5.0, 5.0); 4];
+
+        const INEQUALITIES: &'static [fn(&[f64; 4]) -> f64] = &[];
+
+        const PENALTY_WEIGHT: f64 = 0.0;
+    }
+
+    #[test]
+    fn workers_do_not_change_results() {
+        let solve = |workers| {
+            let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+            let termination = Termination::iterations(30);
+
+            let ga = genetic_algorithm::<_, Sphere, _, _, _, _, 4>(
+                &mut rng,
+                termination,
+                &GeneticAlgorithm {
+                    population_size: 21,
+                    selection: Tournament::new(2),
+                    crossover: SimulatedBinary::new(15.0),
+                    crossover_probability: 0.9,
+                    mutation: Polynomial::new(0.25, 20.0),
+                    replacement: Replacement::Generational { elitism: 1 },
+                    workers,
+                },
+                &mut (),
+            );
+            let de = differential_evolution::<_, Sphere, _, 4>(
+                &mut rng,
+                termination,
+                &DifferentialEvolution {
+                    population_size: 21,
+                    crossover_probability: 0.9,
+                    differential_weight: 0.5,
+                    workers,
+                },
+                &mut (),
+            );
+
+            (ga, de)
+        };
+
+        assert_eq!(solve(1), solve(4));
     }
 }
@@ -0,0 +1,472 @@
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+
+use super::{Population, Range};
+
+/// Watches a run. Every hook gets read access to the whole population, and
+/// [`generation`](Observer::generation) can stop the run early by returning
+/// `ControlFlow::Break`.
+///
+/// `()` observes nothing, `&mut O` lets the caller inspect an observer after
+/// the run, and a pair calls both of its observers in turn.
+pub trait Observer<const D: usize> {
+    /// Called once before the first iteration.
+    fn initialize(&mut self, _population: &Population<D>) {}
+
+    /// Called after every iteration.
+    fn generation(&mut self, _population: &Population<D>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called once when the run ends, whatever the reason.
+    fn terminate(&mut self, _population: &Population<D>) {}
+}
+
+impl<const D: usize> Observer<D> for () {}
+
+impl<O, const D: usize> Observer<D> for &mut O
+where
+    O: Observer<D>,
+{
+    fn initialize(&mut self, population: &Population<D>) {
+        (**self).initialize(population);
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        (**self).generation(population)
+    }
+
+    fn terminate(&mut self, population: &Population<D>) {
+        (**self).terminate(population);
+    }
+}
+
+impl<A, B, const D: usize> Observer<D> for (A, B)
+where
+    A: Observer<D>,
+    B: Observer<D>,
+{
+    fn initialize(&mut self, population: &Population<D>) {
+        self.0.initialize(population);
+        self.1.initialize(population);
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        let a = self.0.generation(population);
+        let b = self.1.generation(population);
+
+        if a.is_break() || b.is_break() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn terminate(&mut self, population: &Population<D>) {
+        self.0.terminate(population);
+        self.1.terminate(population);
+    }
+}
+
+/// Prints the cost and the best value so far to the standard error every
+/// `interval` iterations.
+pub struct Progress {
+    interval: usize,
+}
+impl Progress {
+    pub fn new(interval: usize) -> Self {
+        Self { interval }
+    }
+}
+
+impl<const D: usize> Observer<D> for Progress {
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        if population.iterations.is_multiple_of(self.interval) {
+            eprintln!(
+                "iteration {:>8}  evaluations {:>10}  best {:e}",
+                population.iterations, population.evaluations, population.best_fitness
+            );
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn terminate(&mut self, population: &Population<D>) {
+        eprintln!(
+            "finished after {} iterations and {} evaluations, best {:e}",
+            population.iterations, population.evaluations, population.best_fitness
+        );
+    }
+}
+
+/// Records the best value so far after every iteration and, if given a
+/// `patience`, stops the run once it has not improved for that many
+/// iterations.
+#[derive(Default)]
+pub struct BestFitness {
+    pub history: Vec<f64>,
+    patience: Option<usize>,
+    stall: usize,
+}
+impl BestFitness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_patience(patience: usize) -> Self {
+        Self {
+            patience: Some(patience),
+            ..Self::default()
+        }
+    }
+}
+
+impl<const D: usize> Observer<D> for BestFitness {
+    fn initialize(&mut self, population: &Population<D>) {
+        self.history.push(population.best_fitness);
+        self.stall = 0;
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        match self.history.last() {
+            Some(&best) if population.best_fitness >= best => self.stall += 1,
+            _ => self.stall = 0,
+        }
+        self.history.push(population.best_fitness);
+
+        match self.patience {
+            Some(patience) if self.stall >= patience => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+/// Records the diversity of the population after every iteration, as the
+/// mean distance of the individuals to their centroid, with every coordinate
+/// normalized by the width of its range.
+pub struct Diversity<const D: usize> {
+    pub history: Vec<f64>,
+    ranges: [Range<f64>; D],
+}
+impl<const D: usize> Diversity<D> {
+    pub fn new(ranges: [Range<f64>; D]) -> Self {
+        Self {
+            history: Vec::new(),
+            ranges,
+        }
+    }
+
+    pub fn measure(&self, individuals: &[[f64; D]]) -> f64 {
+        let n = individuals.len() as f64;
+
+        let mut centroid = [0.0; D];
+        for individual in individuals {
+            for (c, x) in centroid.iter_mut().zip(individual) {
+                *c += x / n;
+            }
+        }
+
+        individuals
+            .iter()
+            .map(|individual| {
+                individual
+                    .iter()
+                    .zip(&centroid)
+                    .zip(&self.ranges)
+                    .map(|((x, c), range)| {
+                        let d = (x - c) / (range.end - range.start);
+                        d * d
+                    })
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .sum::<f64>()
+            / n
+    }
+}
+
+impl<const D: usize> Observer<D> for Diversity<D> {
+    fn initialize(&mut self, population: &Population<D>) {
+        self.history.push(self.measure(&population.individuals));
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        self.history.push(self.measure(&population.individuals));
+        ControlFlow::Continue(())
+    }
+}
+
+/// Writes one CSV row per iteration with the cost so far, the best value so
+/// far and the minimum, mean and maximum fitness of the population. A failed
+/// write stops the run, and the error is returned by
+/// [`into_inner`](Csv::into_inner).
+pub struct Csv<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+impl<W: Write> Csv<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    pub fn into_inner(self) -> io::Result<W> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.writer),
+        }
+    }
+
+    fn try_write<const D: usize>(&mut self, population: &Population<D>) -> io::Result<()> {
+        let fitnesses = &population.fitnesses;
+
+        let min = fitnesses.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = fitnesses.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = fitnesses.iter().sum::<f64>() / fitnesses.len() as f64;
+
+        writeln!(
+            self.writer,
+            "{},{},{},{min},{mean},{max}",
+            population.iterations, population.evaluations, population.best_fitness
+        )
+    }
+
+    fn record<const D: usize>(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        if self.error.is_none() {
+            if let Err(error) = self.try_write(population) {
+                self.error = Some(error);
+            }
+        }
+
+        if self.error.is_some() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+impl<W: Write, const D: usize> Observer<D> for Csv<W> {
+    fn initialize(&mut self, population: &Population<D>) {
+        if let Err(error) = writeln!(
+            self.writer,
+            "iterations,evaluations,best_fitness,min,mean,max"
+        ) {
+            self.error = Some(error);
+        }
+        let _ = self.record(population);
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        self.record(population)
+    }
+
+    fn terminate(&mut self, _population: &Population<D>) {
+        if self.error.is_none() {
+            if let Err(error) = self.writer.flush() {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+/// Records the best value so far against the number of evaluations spent,
+/// every time it improves and once more at the end of the run, so that
+/// `points` describe a step function.
+#[derive(Default)]
+pub struct History {
+    pub points: Vec<(usize, f64)>,
+}
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the points as `evaluations,best_fitness` rows under a header.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "evaluations,best_fitness")?;
+        for &(evaluations, best_fitness) in &self.points {
+            writeln!(writer, "{evaluations},{best_fitness:?}")?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the points as `{"evaluations": [...], "best_fitness": [...]}`,
+    /// with values that are not finite written as `null`.
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{{\"evaluations\": [")?;
+        for (i, &(evaluations, _)) in self.points.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(writer, "{separator}{evaluations}")?;
+        }
+
+        write!(writer, "], \"best_fitness\": [")?;
+        for (i, &(_, best_fitness)) in self.points.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            if best_fitness.is_finite() {
+                write!(writer, "{separator}{best_fitness:?}")?;
+            } else {
+                write!(writer, "{separator}null")?;
+            }
+        }
+        writeln!(writer, "]}}")?;
+
+        writer.flush()
+    }
+}
+
+impl<const D: usize> Observer<D> for History {
+    fn initialize(&mut self, population: &Population<D>) {
+        self.points
+            .push((population.evaluations, population.best_fitness));
+    }
+
+    fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+        match self.points.last() {
+            Some(&(_, best)) if population.best_fitness >= best => {}
+            _ => self
+                .points
+                .push((population.evaluations, population.best_fitness)),
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn terminate(&mut self, population: &Population<D>) {
+        if self.points.last().map(|&(evaluations, _)| evaluations) != Some(population.evaluations) {
+            self.points
+                .push((population.evaluations, population.best_fitness));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::evolutionary_computation::*;
+    use crate::metaheuristics::tests::Sphere;
+    use crate::metaheuristics::Termination;
+    use rand::rngs::Xoshiro256Plus;
+
+    struct StopAt(usize);
+
+    impl<const D: usize> Observer<D> for StopAt {
+        fn generation(&mut self, population: &Population<D>) -> ControlFlow<()> {
+            if population.iterations >= self.0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    fn population(individuals: Vec<[f64; 2]>, fitnesses: Vec<f64>) -> Population<2> {
+        let best_fitness = fitnesses.iter().copied().fold(f64::INFINITY, f64::min);
+        Population {
+            ages: vec![0; individuals.len()],
+            best_individual: individuals[0],
+            evaluations: individuals.len(),
+            individuals,
+            fitnesses,
+            best_fitness,
+            iterations: 0,
+            parameters: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn break_stops_the_run() {
+        let mut best = BestFitness::new();
+        let mut observer = (&mut best, StopAt(5));
+
+        let solution = genetic_algorithm::<_, Sphere, _, _, _, _, 4>(
+            &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+            Termination::iterations(100),
+            &GeneticAlgorithm {
+                population_size: 10,
+                selection: Tournament::new(2),
+                crossover: Arithmetic,
+                crossover_probability: 0.9,
+                mutation: UniformReset::new(0.1),
+                replacement: Replacement::Generational { elitism: 1 },
+                workers: 1,
+            },
+            &mut observer,
+        );
+
+        // both observers see every iteration of the run, and the first one
+        // records the initial population too
+        assert_eq!(solution.iterations, 5);
+        assert_eq!(best.history.len(), 6);
+        assert_eq!(*best.history.last().unwrap(), solution.fitness);
+    }
+
+    #[test]
+    fn patience_works() {
+        let mut observer = BestFitness::with_patience(2);
+        let mut population = population(vec![[0.0, 0.0]; 2], vec![3.0, 4.0]);
+        Observer::initialize(&mut observer, &population);
+
+        let mut stops = Vec::new();
+        for best_fitness in [2.0, 2.0, 1.0, 1.0, 1.0] {
+            population.best_fitness = best_fitness;
+            stops.push(observer.generation(&population).is_break());
+        }
+
+        assert_eq!(stops, [false, false, false, false, true]);
+        assert_eq!(observer.history, [3.0, 2.0, 2.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn diversity_works() {
+        let diversity = Diversity::new([Range::new(0.0, 2.0), Range::new(0.0, 4.0)]);
+
+        // both points are at (0.5, 0.5) from the centroid once normalized
+        let individuals = [[0.0, 0.0], [2.0, 4.0]];
+        assert!((diversity.measure(&individuals) - f64::sqrt(0.5)).abs() < 1e-12);
+        assert_eq!(diversity.measure(&[[1.0, 1.0]; 3]), 0.0);
+    }
+
+    #[test]
+    fn csv_works() {
+        let mut csv = Csv::new(Vec::new());
+        let mut population = population(vec![[0.0, 0.0]; 3], vec![1.0, 2.0, 6.0]);
+
+        Observer::initialize(&mut csv, &population);
+        population.iterations = 1;
+        population.evaluations = 6;
+        population.fitnesses = vec![0.5, 2.0, 2.5];
+        population.best_fitness = 0.5;
+        assert!(csv.generation(&population).is_continue());
+        Observer::terminate(&mut csv, &population);
+
+        let text = String::from_utf8(csv.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            text,
+            "iterations,evaluations,best_fitness,min,mean,max\n\
+             0,3,1,1,3,6\n\
+             1,6,0.5,0.5,1.6666666666666667,2.5\n"
+        );
+    }
+
+    #[test]
+    fn history_is_exported() {
+        let history = History {
+            points: vec![(10, 5.0), (20, 1.5), (30, f64::INFINITY)],
+        };
+
+        let mut csv = Vec::new();
+        history.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "evaluations,best_fitness\n10,5.0\n20,1.5\n30,inf\n"
+        );
+
+        let mut json = Vec::new();
+        history.write_json(&mut json).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "{\"evaluations\": [10, 20, 30], \"best_fitness\": [5.0, 1.5, null]}\n"
+        );
+    }
+}
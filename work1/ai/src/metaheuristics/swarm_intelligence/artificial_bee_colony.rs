@@ -0,0 +1,502 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{deb_better, quality, roulette};
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, evaluate_with, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Artificial bee colony of Karaboga. Every food source has an employed bee
+/// that searches around it, onlooker bees then search around sources chosen
+/// in proportion to their quality, and a scout replaces the source that went
+/// longest without improving once that exceeds `limit` trials.
+///
+/// Unlike Karaboga's sequential colony, the bees of a phase all search from
+/// the same sources and are evaluated together, so that `workers` can share
+/// them. The onlookers thus pick their sources from the qualities left by the
+/// employed bees, and none of them sees the moves of the others until the
+/// next phase.
+///
+/// The trials of every source are kept in the ages of the population.
+pub struct ArtificialBeeColony {
+    /// Number of food sources, which is also the number of employed and of
+    /// onlooker bees.
+    pub colony_size: usize,
+    pub limit: usize,
+    /// Number of threads evaluating the bees of a phase.
+    pub workers: usize,
+}
+
+pub fn artificial_bee_colony<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &ArtificialBeeColony,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for ArtificialBeeColony {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        assert!(
+            self.colony_size >= 2,
+            "the colony needs at least two sources"
+        );
+        Population::random::<R, P>(rng, self.colony_size, self.workers)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+
+        let employed = (0..n).collect::<Vec<_>>();
+        self.search::<R, P, D>(rng, population, &employed);
+
+        // onlookers choose among the sources left by the employed bees
+        let weights = population
+            .fitnesses
+            .iter()
+            .map(|&f| quality(f))
+            .collect::<Vec<_>>();
+        let onlookers = (0..n).map(|_| roulette(rng, &weights)).collect::<Vec<_>>();
+        self.search::<R, P, D>(rng, population, &onlookers);
+
+        if let Some(i) = exhausted(&population.ages, self.limit) {
+            let scout = Population::<D>::random::<R, P>(rng, 1, 1);
+
+            population.individuals[i] = scout.individuals[0];
+            population.fitnesses[i] = scout.fitnesses[0];
+            population.ages[i] = 0;
+            population.evaluations += 1;
+
+            population.update_best(&scout.individuals[0], scout.fitnesses[0]);
+        }
+
+        population.iterations += 1;
+    }
+}
+
+impl ArtificialBeeColony {
+    // the bees of a phase search around `sources` at the same time, so that
+    // they can be evaluated together, and replace them when they improve
+    fn search<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        sources: &[usize],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let candidates = sources
+            .iter()
+            .map(|&i| neighbor::<R, P, D>(rng, &population.individuals, i, 0.0))
+            .collect::<Vec<_>>();
+
+        let mut fitnesses = vec![0.0; sources.len()];
+        evaluate::<P, D>(&candidates, &mut fitnesses, self.workers);
+        population.evaluations += sources.len();
+
+        for ((&i, candidate), fitness) in sources.iter().zip(&candidates).zip(fitnesses) {
+            if fitness < population.fitnesses[i] {
+                population.individuals[i] = *candidate;
+                population.fitnesses[i] = fitness;
+                population.ages[i] = 0;
+
+                population.update_best(candidate, fitness);
+            } else {
+                population.ages[i] += 1;
+            }
+        }
+    }
+}
+
+/// Constrained artificial bee colony of Karaboga and Akay, which compares
+/// points with Deb's feasibility rules instead of penalties. Searching
+/// around a source changes each variable with probability
+/// `modification_rate`, and at least one, and onlookers prefer feasible
+/// sources. Every `scout_production_period` iterations, scouts replace all
+/// the sources past `limit` trials. Bees search in phases as in
+/// [`ArtificialBeeColony`].
+///
+/// The fitnesses of the population are objective values and the best
+/// fitness is `phi`, which is the objective value once a feasible point has
+/// been found. Violations are computed again when needed, since constraints
+/// are taken to be cheap next to the objective.
+pub struct ConstrainedArtificialBeeColony {
+    pub colony_size: usize,
+    pub limit: usize,
+    pub modification_rate: f64,
+    pub scout_production_period: usize,
+    /// Number of threads evaluating the bees of a phase.
+    pub workers: usize,
+}
+
+pub fn constrained_artificial_bee_colony<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &ConstrainedArtificialBeeColony,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for ConstrainedArtificialBeeColony {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        assert!(
+            self.colony_size >= 2,
+            "the colony needs at least two sources"
+        );
+        assert!(
+            self.scout_production_period > 0,
+            "the scout production period must be positive"
+        );
+
+        let individuals = (0..self.colony_size)
+            .map(|_| random_point::<R, P, D>(rng))
+            .collect::<Vec<_>>();
+        let mut fitnesses = vec![0.0; self.colony_size];
+        evaluate_with(&individuals, &mut fitnesses, self.workers, P::f);
+
+        let mut population = Population {
+            individuals,
+            fitnesses,
+            ages: vec![0; self.colony_size],
+            best_individual: [0.0; D],
+            best_fitness: f64::INFINITY,
+            iterations: 0,
+            evaluations: self.colony_size,
+            parameters: Vec::new(),
+        };
+
+        for i in 0..self.colony_size {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            update_best::<P, D>(&mut population, &x, f);
+        }
+
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+
+        let employed = (0..n).collect::<Vec<_>>();
+        self.search::<R, P, D>(rng, population, &employed);
+
+        // feasible sources weigh between 0.5 and 1, by quality, infeasible
+        // ones between 0 and 0.5, by violation
+        let violations = population
+            .individuals
+            .iter()
+            .map(P::violation)
+            .collect::<Vec<_>>();
+        let total_quality = population
+            .fitnesses
+            .iter()
+            .map(|&f| quality(f))
+            .sum::<f64>();
+        let total_violation = violations.iter().sum::<f64>();
+
+        let weights = population
+            .fitnesses
+            .iter()
+            .zip(&violations)
+            .map(|(&f, &violation)| {
+                if violation == 0.0 {
+                    0.5 + 0.5 * quality(f) / total_quality
+                } else {
+                    0.5 * (1.0 - violation / total_violation)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let onlookers = (0..n).map(|_| roulette(rng, &weights)).collect::<Vec<_>>();
+        self.search::<R, P, D>(rng, population, &onlookers);
+
+        population.iterations += 1;
+
+        if population
+            .iterations
+            .is_multiple_of(self.scout_production_period)
+        {
+            for i in 0..n {
+                if population.ages[i] <= self.limit {
+                    continue;
+                }
+
+                let x = random_point::<R, P, D>(rng);
+                let f = P::f(&x);
+
+                population.individuals[i] = x;
+                population.fitnesses[i] = f;
+                population.ages[i] = 0;
+                population.evaluations += 1;
+
+                update_best::<P, D>(population, &x, f);
+            }
+        }
+    }
+}
+
+impl ConstrainedArtificialBeeColony {
+    // the bees of a phase search around `sources` at the same time, so that
+    // they can be evaluated together, and replace them when Deb's rules
+    // prefer the new points
+    fn search<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        sources: &[usize],
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let candidates = sources
+            .iter()
+            .map(|&i| neighbor::<R, P, D>(rng, &population.individuals, i, self.modification_rate))
+            .collect::<Vec<_>>();
+
+        let mut fitnesses = vec![0.0; sources.len()];
+        evaluate_with(&candidates, &mut fitnesses, self.workers, P::f);
+        population.evaluations += sources.len();
+
+        for ((&i, x), f) in sources.iter().zip(&candidates).zip(fitnesses) {
+            let violation = P::violation(x);
+            let source = &population.individuals[i];
+
+            if deb_better(f, violation, population.fitnesses[i], P::violation(source)) {
+                population.individuals[i] = *x;
+                population.fitnesses[i] = f;
+                population.ages[i] = 0;
+
+                update_best::<P, D>(population, x, f);
+            } else {
+                population.ages[i] += 1;
+            }
+        }
+    }
+}
+
+// records `x` as the best point if Deb's rules prefer it, with a best fitness
+// of its objective value penalized as in `phi`
+fn update_best<P, const D: usize>(population: &mut Population<D>, x: &[f64; D], f: f64)
+where
+    P: Problem<D>,
+{
+    let violation = P::violation(x);
+    let best_violation = P::violation(&population.best_individual);
+
+    // while the best point is feasible its fitness is its objective value
+    if population.best_fitness == f64::INFINITY
+        || deb_better(f, violation, population.best_fitness, best_violation)
+    {
+        population.best_individual = *x;
+        population.best_fitness = f + P::PENALTY_WEIGHT * violation;
+    }
+}
+
+// a point around source `i`, moved towards or away from another random source
+// along one random variable, and along each other one with probability
+// `modification_rate`, then clamped to the ranges
+fn neighbor<R, P, const D: usize>(
+    rng: &mut R,
+    sources: &[[f64; D]],
+    i: usize,
+    modification_rate: f64,
+) -> [f64; D]
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+{
+    let k = rng.sample(&UniformClosedOpen::new(
+        0 as f64,
+        (sources.len() - 1) as f64,
+    )) as usize;
+    let k = if k >= i { k + 1 } else { k };
+
+    let r = rng.sample(&UniformClosedOpen::new(0 as f64, D as f64)) as usize;
+
+    let mut x = sources[i];
+    for j in 0..D {
+        if j == r || rng.sample::<f64, _>(&UniformClosedOpen01) < modification_rate {
+            let phi = rng.sample(&UniformClosedOpen::new(-1.0, 1.0));
+            x[j] += phi * (sources[i][j] - sources[k][j]);
+            x[j] = x[j].clamp(P::RANGES[j].start, P::RANGES[j].end);
+        }
+    }
+    x
+}
+
+fn random_point<R, P, const D: usize>(rng: &mut R) -> [f64; D]
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+{
+    let mut x = [0.0; D];
+    for (xj, range) in x.iter_mut().zip(&P::RANGES) {
+        *xj = rng.sample(&UniformClosedOpen::new(range.start, range.end));
+    }
+    x
+}
+
+// the source that went longest without improving, if past the limit
+fn exhausted(trials: &[usize], limit: usize) -> Option<usize> {
+    let (i, &most) = trials
+        .iter()
+        .enumerate()
+        .max_by_key(|&(_, &trials)| trials)?;
+
+    (most > limit).then_some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn in_ranges(population: &Population<4>) -> bool {
+        population
+            .individuals
+            .iter()
+            .flatten()
+            .all(|x| (-5.0..=5.0).contains(x))
+    }
+
+    #[test]
+    fn artificial_bee_colony_works() {
+        let solve = |workers| {
+            let parameters = ArtificialBeeColony {
+                colony_size: 20,
+                limit: 50,
+                workers,
+            };
+            let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+            let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+            let termination = Termination::evaluations(20_000);
+
+            while !population.is_done(&termination) {
+                let evaluations = population.evaluations;
+                parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+                // a batch of employed bees, one of onlookers, and a scout at
+                // most
+                let spent = population.evaluations - evaluations;
+                assert!(spent == 40 || spent == 41, "{spent}");
+                assert!(in_ranges(&population));
+            }
+            population.solution()
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-6, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn constrained_artificial_bee_colony_works() {
+        let solve = |workers| {
+            constrained_artificial_bee_colony::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(20_000),
+                &ConstrainedArtificialBeeColony {
+                    colony_size: 20,
+                    limit: 50,
+                    modification_rate: 0.8,
+                    scout_production_period: 10,
+                    workers,
+                },
+                &mut (),
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-6, "{solution:?}");
+        assert!(solution.x.iter().all(|x| (-5.0..=5.0).contains(x)));
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn scouts_wait_for_their_period() {
+        let parameters = ConstrainedArtificialBeeColony {
+            colony_size: 10,
+            limit: 0,
+            modification_rate: 0.8,
+            scout_production_period: 5,
+            workers: 1,
+        };
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        let termination = Termination::iterations(20);
+
+        let mut scouted = 0;
+        while !population.is_done(&termination) {
+            let evaluations = population.evaluations;
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+            let scouts = population.evaluations - evaluations - 20;
+            if population.iterations.is_multiple_of(5) {
+                // every source that failed since the last period is replaced
+                assert!(population.ages.iter().all(|&trials| trials == 0));
+                assert!(scouts > 0 && scouts <= 10);
+                scouted += scouts;
+            } else {
+                assert_eq!(scouts, 0);
+                // even though sources past the limit are waiting
+                assert!(population.ages.iter().any(|&trials| trials > 0));
+            }
+        }
+        assert!(scouted > 0);
+    }
+}
@@ -0,0 +1,203 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::clamp;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Bat algorithm of Yang. Every bat draws a frequency from `frequencies`,
+/// which scales how much its velocity is pulled by its distance to the best
+/// bat. Unless it emits a pulse, which happens with its pulse rate, it
+/// instead flies to a random point around the best bat, at most the average
+/// loudness away along each variable. The new point replaces the bat if it
+/// is not worse and the bat is loud enough, in which case its loudness is
+/// multiplied by `loudness_decay` and its pulse rate rises towards
+/// `pulse_rate` as `1 - e^(-pulse_rate_growth * t)`.
+///
+/// The parameters of the population keep, for every bat, its loudness and
+/// pulse rate followed by its velocity.
+pub struct BatAlgorithm {
+    pub population_size: usize,
+    pub frequencies: (f64, f64),
+    pub loudness: f64,
+    /// Factor `α` applied to the loudness of a bat when it moves.
+    pub loudness_decay: f64,
+    pub pulse_rate: f64,
+    /// Growth `γ` of the pulse rate of a bat when it moves.
+    pub pulse_rate_growth: f64,
+    /// Number of threads evaluating the bats.
+    pub workers: usize,
+}
+
+pub fn bat_algorithm<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &BatAlgorithm,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for BatAlgorithm {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Population::random::<R, P>(rng, self.population_size, self.workers);
+
+        population.parameters = vec![0.0; self.population_size * (D + 2)];
+        for bat in population.parameters.chunks_exact_mut(D + 2) {
+            bat[0] = self.loudness;
+        }
+
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+        let best = population.best_individual;
+        let (low, high) = self.frequencies;
+
+        let average_loudness = population
+            .parameters
+            .chunks_exact(D + 2)
+            .map(|bat| bat[0])
+            .sum::<f64>()
+            / n as f64;
+
+        let mut candidates = population.individuals.clone();
+        for (x, bat) in candidates
+            .iter_mut()
+            .zip(population.parameters.chunks_exact_mut(D + 2))
+        {
+            let frequency = low + (high - low) * rng.sample::<f64, _>(&UniformClosedOpen01);
+            let velocity = &mut bat[2..];
+
+            for ((xj, vj), &bj) in x.iter_mut().zip(velocity).zip(&best) {
+                *vj += (*xj - bj) * frequency;
+                *xj += *vj;
+            }
+
+            if rng.sample::<f64, _>(&UniformClosedOpen01) > bat[1] {
+                for (xj, &bj) in x.iter_mut().zip(&best) {
+                    let epsilon = rng.sample(&UniformClosedOpen::new(-1.0, 1.0));
+                    *xj = bj + epsilon * average_loudness;
+                }
+            }
+
+            clamp::<P, D>(x);
+        }
+
+        let mut fitnesses = vec![0.0; n];
+        evaluate::<P, D>(&candidates, &mut fitnesses, self.workers);
+        population.evaluations += n;
+
+        let t = (population.iterations + 1) as f64;
+        for (i, (x, f)) in candidates.iter().zip(fitnesses).enumerate() {
+            let bat = &mut population.parameters[i * (D + 2)..(i + 1) * (D + 2)];
+
+            if f <= population.fitnesses[i] && rng.sample::<f64, _>(&UniformClosedOpen01) < bat[0] {
+                bat[0] *= self.loudness_decay;
+                bat[1] = self.pulse_rate * (1.0 - f64::exp(-self.pulse_rate_growth * t));
+
+                population.individuals[i] = *x;
+                population.fitnesses[i] = f;
+            }
+
+            population.update_best(x, f);
+        }
+
+        population.iterations += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn bats(workers: usize) -> BatAlgorithm {
+        BatAlgorithm {
+            population_size: 20,
+            frequencies: (0.0, 2.0),
+            loudness: 1.0,
+            loudness_decay: 0.9,
+            pulse_rate: 0.5,
+            pulse_rate_growth: 0.9,
+            workers,
+        }
+    }
+
+    #[test]
+    fn bat_algorithm_works() {
+        let solve = |workers| {
+            bat_algorithm::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(20_000),
+                &bats(workers),
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-2, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn loudness_and_pulse_rate_work() {
+        let parameters = bats(1);
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        let termination = Termination::iterations(50);
+
+        while !population.is_done(&termination) {
+            let before = population.parameters.clone();
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+            // a bat that moves gets quieter and pulses more often
+            let t = population.iterations as f64;
+            for (bat, old) in population
+                .parameters
+                .chunks_exact(6)
+                .zip(before.chunks_exact(6))
+            {
+                if bat[0] != old[0] {
+                    assert_eq!(bat[0], old[0] * 0.9);
+                    assert_eq!(bat[1], 0.5 * (1.0 - f64::exp(-0.9 * t)));
+                } else {
+                    assert_eq!(bat[1], old[1]);
+                }
+            }
+        }
+        assert!(population
+            .parameters
+            .chunks_exact(6)
+            .all(|bat| bat[0] < 1.0));
+    }
+}
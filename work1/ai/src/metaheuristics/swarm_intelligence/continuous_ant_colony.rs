@@ -0,0 +1,186 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::{clamp, roulette};
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Ant colony optimization for continuous domains, ACO_R, of Socha and
+/// Dorigo. The population is an archive of solutions ranked by `phi`, which
+/// defines a Gaussian kernel per solution and variable. Every ant picks a
+/// guiding solution by roulette, the one of rank `l` (from 0) weighing
+/// `e^(-l^2 / (2 q^2 k^2))` for an archive of `k` solutions, and samples each
+/// variable from a normal centered on it, with a standard deviation of
+/// `convergence_speed` times the average distance from it to the rest of the
+/// archive. The ants then compete with the archive for its `k` places.
+pub struct ContinuousAntColony {
+    pub archive_size: usize,
+    pub ants: usize,
+    /// Locality `q` of the search, the smaller the more the best solutions
+    /// guide the ants.
+    pub locality: f64,
+    /// Convergence speed `ξ`, the larger the slower the archive converges.
+    pub convergence_speed: f64,
+    /// Number of threads evaluating the ants.
+    pub workers: usize,
+}
+
+pub fn continuous_ant_colony<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &ContinuousAntColony,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for ContinuousAntColony {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Population::random::<R, P>(rng, self.archive_size, self.workers);
+
+        let archive = population
+            .individuals
+            .iter()
+            .copied()
+            .zip(population.fitnesses.iter().copied())
+            .collect();
+        rank(&mut population, archive, self.archive_size);
+
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let k = population.individuals.len();
+        let spread = 2.0 * (self.locality * k as f64).powi(2);
+        let weights = (0..k)
+            .map(|l| f64::exp(-((l * l) as f64) / spread))
+            .collect::<Vec<_>>();
+
+        let mut ants = vec![[0.0; D]; self.ants];
+        for ant in &mut ants {
+            let guide = &population.individuals[roulette(rng, &weights)];
+
+            for (j, xj) in ant.iter_mut().enumerate() {
+                let distance = population
+                    .individuals
+                    .iter()
+                    .map(|x| (x[j] - guide[j]).abs())
+                    .sum::<f64>();
+                let deviation = self.convergence_speed * distance / (k - 1).max(1) as f64;
+
+                *xj = rng.sample(&Normal::new(guide[j], deviation));
+            }
+
+            clamp::<P, D>(ant);
+        }
+
+        let mut fitnesses = vec![0.0; self.ants];
+        evaluate::<P, D>(&ants, &mut fitnesses, self.workers);
+        population.evaluations += self.ants;
+
+        let mut archive = population
+            .individuals
+            .iter()
+            .copied()
+            .zip(population.fitnesses.iter().copied())
+            .collect::<Vec<_>>();
+        archive.extend(ants.into_iter().zip(fitnesses));
+        rank(population, archive, k);
+
+        population.iterations += 1;
+    }
+}
+
+// keeps the best `size` solutions of `archive` as the population, best first
+fn rank<const D: usize>(
+    population: &mut Population<D>,
+    mut archive: Vec<([f64; D], f64)>,
+    size: usize,
+) {
+    archive.sort_by(|a, b| a.1.total_cmp(&b.1));
+    archive.truncate(size);
+
+    population.individuals = archive.iter().map(|&(x, _)| x).collect();
+    population.fitnesses = archive.iter().map(|&(_, f)| f).collect();
+    population.update_best(&archive[0].0, archive[0].1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn colony(workers: usize) -> ContinuousAntColony {
+        ContinuousAntColony {
+            archive_size: 20,
+            ants: 4,
+            locality: 0.1,
+            convergence_speed: 0.85,
+            workers,
+        }
+    }
+
+    #[test]
+    fn continuous_ant_colony_works() {
+        let solve = |workers| {
+            continuous_ant_colony::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(10_000),
+                &colony(workers),
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-10, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn archive_stays_ranked() {
+        let parameters = colony(1);
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        let termination = Termination::iterations(30);
+
+        while !population.is_done(&termination) {
+            let worst = population.fitnesses[19];
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+            // the ants only ever push out the worst solutions
+            assert_eq!(population.individuals.len(), 20);
+            assert!(population.fitnesses.windows(2).all(|w| w[0] <= w[1]));
+            assert!(population.fitnesses[19] <= worst);
+            assert_eq!(population.best_individual, population.individuals[0]);
+            assert_eq!(population.best_fitness, population.fitnesses[0]);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use rand::distributions::{Distribution, Levy, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+use rand::util::shuffle;
+
+use super::clamp;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Cuckoo search of Yang and Deb. Every cuckoo lays an egg a Lévy flight
+/// away from its nest, with steps proportional to the distance to the best
+/// nest, and the egg replaces the nest if it is better. Then each variable of
+/// each nest is discovered with probability `abandon_probability`, and moved
+/// by a random fraction of the difference between two random nests.
+pub struct CuckooSearch {
+    pub population_size: usize,
+    /// Probability `pa` of discovering each variable of a nest.
+    pub abandon_probability: f64,
+    /// Index of the Lévy flights, usually 1.5.
+    pub beta: f64,
+    /// Scale of the Lévy flights relative to the distance to the best nest,
+    /// usually 0.01.
+    pub step_size: f64,
+    /// Number of threads evaluating the eggs.
+    pub workers: usize,
+}
+
+pub fn cuckoo_search<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &CuckooSearch,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for CuckooSearch {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        Population::random::<R, P>(rng, self.population_size, self.workers)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+        let levy = Levy::<f64>::new(self.beta);
+        let best = population.best_individual;
+
+        let mut eggs = population.individuals.clone();
+        for egg in &mut eggs {
+            for (xj, &bj) in egg.iter_mut().zip(&best) {
+                *xj += self.step_size * rng.sample(&levy) * (*xj - bj);
+            }
+            clamp::<P, D>(egg);
+        }
+        replace_worse::<P, D>(population, &eggs, self.workers);
+
+        let mut first = (0..n).collect::<Vec<_>>();
+        let mut second = (0..n).collect::<Vec<_>>();
+        shuffle(rng, &mut first);
+        shuffle(rng, &mut second);
+
+        let mut eggs = population.individuals.clone();
+        for (egg, (&k, &l)) in eggs.iter_mut().zip(first.iter().zip(&second)) {
+            let r = rng.sample::<f64, _>(&UniformClosedOpen01);
+            for (j, xj) in egg.iter_mut().enumerate() {
+                if rng.sample::<f64, _>(&UniformClosedOpen01) < self.abandon_probability {
+                    *xj += r * (population.individuals[k][j] - population.individuals[l][j]);
+                }
+            }
+            clamp::<P, D>(egg);
+        }
+        replace_worse::<P, D>(population, &eggs, self.workers);
+
+        population.iterations += 1;
+    }
+}
+
+// evaluates one egg per nest, each replacing its nest if it is better
+fn replace_worse<P, const D: usize>(
+    population: &mut Population<D>,
+    eggs: &[[f64; D]],
+    workers: usize,
+) where
+    P: Problem<D>,
+{
+    let mut fitnesses = vec![0.0; eggs.len()];
+    evaluate::<P, D>(eggs, &mut fitnesses, workers);
+    population.evaluations += eggs.len();
+
+    for (i, (egg, fitness)) in eggs.iter().zip(fitnesses).enumerate() {
+        if fitness < population.fitnesses[i] {
+            population.individuals[i] = *egg;
+            population.fitnesses[i] = fitness;
+            population.update_best(egg, fitness);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn cuckoos(workers: usize) -> CuckooSearch {
+        CuckooSearch {
+            population_size: 25,
+            abandon_probability: 0.25,
+            beta: 1.5,
+            step_size: 0.01,
+            workers,
+        }
+    }
+
+    #[test]
+    fn cuckoo_search_works() {
+        let solve = |workers| {
+            cuckoo_search::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(20_000),
+                &cuckoos(workers),
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-6, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn nests_only_improve() {
+        let parameters = cuckoos(1);
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        let termination = Termination::iterations(50);
+
+        while !population.is_done(&termination) {
+            let fitnesses = population.fitnesses.clone();
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+
+            // an egg for every nest in each of the two phases
+            assert_eq!(population.evaluations, 25 * (1 + 2 * population.iterations));
+            for (new, old) in population.fitnesses.iter().zip(&fitnesses) {
+                assert!(new <= old);
+            }
+        }
+    }
+}
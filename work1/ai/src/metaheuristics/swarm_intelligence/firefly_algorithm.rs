@@ -0,0 +1,181 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::clamp;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Firefly algorithm of Yang. Every firefly moves towards each brighter one
+/// with an attractiveness of `attractiveness * e^(-absorption * r^2)` at
+/// distance `r`, plus a random step of `randomization` times the width of
+/// each range, and the brightest one only takes the random step. The
+/// randomization is multiplied by `randomization_decay` after every
+/// iteration.
+///
+/// The fireflies are ranked once per iteration and move towards the
+/// positions the brighter ones had at its start, so an iteration costs
+/// `N(N - 1) / 2` moves but only `N` evaluations. The current randomization
+/// is kept in the parameters of the population.
+pub struct FireflyAlgorithm {
+    pub population_size: usize,
+    /// Attractiveness `β0` at distance zero, usually 1.
+    pub attractiveness: f64,
+    /// Light absorption coefficient `γ`, usually of the order of one over
+    /// the squared width of the ranges.
+    pub absorption: f64,
+    /// Initial randomization `α`, relative to the width of the ranges.
+    pub randomization: f64,
+    /// Factor applied to the randomization after every iteration, usually
+    /// slightly below 1.
+    pub randomization_decay: f64,
+    /// Number of threads evaluating the fireflies.
+    pub workers: usize,
+}
+
+pub fn firefly_algorithm<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &FireflyAlgorithm,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for FireflyAlgorithm {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Population::random::<R, P>(rng, self.population_size, self.workers);
+        population.parameters = vec![self.randomization];
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        _termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let alpha = population.parameters[0];
+
+        // brightest first, so that every firefly only looks at those before it
+        let mut ranking = (0..population.individuals.len()).collect::<Vec<_>>();
+        ranking.sort_by(|&a, &b| population.fitnesses[a].total_cmp(&population.fitnesses[b]));
+        let fireflies = ranking
+            .iter()
+            .map(|&i| population.individuals[i])
+            .collect::<Vec<_>>();
+
+        let mut moved = fireflies.clone();
+        for (rank, x) in moved.iter_mut().enumerate() {
+            for brighter in &fireflies[..rank] {
+                let r2 = brighter
+                    .iter()
+                    .zip(x.iter())
+                    .map(|(b, xj)| (b - xj) * (b - xj))
+                    .sum::<f64>();
+                let beta = self.attractiveness * f64::exp(-self.absorption * r2);
+
+                for (j, xj) in x.iter_mut().enumerate() {
+                    *xj += beta * (brighter[j] - *xj) + random_step::<R, P, D>(rng, alpha, j);
+                }
+            }
+
+            if rank == 0 {
+                for (j, xj) in x.iter_mut().enumerate() {
+                    *xj += random_step::<R, P, D>(rng, alpha, j);
+                }
+            }
+
+            clamp::<P, D>(x);
+        }
+
+        population.individuals = moved;
+        evaluate::<P, D>(
+            &population.individuals,
+            &mut population.fitnesses,
+            self.workers,
+        );
+        population.evaluations += population.individuals.len();
+
+        for i in 0..population.individuals.len() {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            population.update_best(&x, f);
+        }
+
+        population.parameters[0] = alpha * self.randomization_decay;
+        population.iterations += 1;
+    }
+}
+
+// uniform in [-alpha / 2, alpha / 2) times the width of range `j`
+fn random_step<R, P, const D: usize>(rng: &mut R, alpha: f64, j: usize) -> f64
+where
+    R: Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+{
+    let width = P::RANGES[j].end - P::RANGES[j].start;
+    alpha * (rng.sample::<f64, _>(&UniformClosedOpen01) - 0.5) * width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn firefly_algorithm_works() {
+        let parameters = |workers| FireflyAlgorithm {
+            population_size: 15,
+            attractiveness: 1.0,
+            absorption: 0.01,
+            randomization: 0.2,
+            randomization_decay: 0.95,
+            workers,
+        };
+        let solve = |workers| {
+            firefly_algorithm::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::iterations(200),
+                &parameters(workers),
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-4, "{solution:?}");
+        assert_eq!(solution.evaluations, 15 * 201);
+        assert_eq!(solution, solve(3));
+
+        // the randomization decays geometrically
+        let parameters = parameters(1);
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let mut population = parameters.initialize::<_, Sphere, 4>(&mut rng);
+        let termination = Termination::iterations(10);
+        for _ in 0..10 {
+            parameters.iterate::<_, Sphere, 4>(&mut rng, &mut population, &termination);
+        }
+        assert!((population.parameters[0] - 0.2 * 0.95f64.powi(10)).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,161 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::clamp;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Grey wolf optimizer of Mirjalili et al. Every wolf moves to the average of
+/// three points, each drawn around one of the alpha, beta and delta leaders,
+/// which are the three best points found so far. How far from the leaders
+/// the points can fall is scaled by `a`, which decreases linearly from 2 to 0
+/// over the budget, turning exploration into exploitation.
+///
+/// The leaders are kept in the parameters of the population, each as its
+/// fitness followed by its position.
+pub struct GreyWolfOptimizer {
+    pub population_size: usize,
+    /// Number of threads evaluating the wolves.
+    pub workers: usize,
+}
+
+pub fn grey_wolf_optimizer<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &GreyWolfOptimizer,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for GreyWolfOptimizer {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let mut population = Population::random::<R, P>(rng, self.population_size, self.workers);
+
+        population.parameters = vec![f64::INFINITY; 3 * (D + 1)];
+        for i in 0..population.individuals.len() {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            update_leaders(&mut population.parameters, &x, f);
+        }
+
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let a = 2.0 * (1.0 - termination.progress(population.iterations, population.evaluations));
+
+        for wolf in &mut population.individuals {
+            let mut x = [0.0; D];
+
+            for leader in population.parameters.chunks_exact(D + 1) {
+                for (j, xj) in x.iter_mut().enumerate() {
+                    let r1 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                    let r2 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                    let big_a = 2.0 * a * r1 - a;
+                    let c = 2.0 * r2;
+
+                    let distance = (c * leader[j + 1] - wolf[j]).abs();
+                    *xj += (leader[j + 1] - big_a * distance) / 3.0;
+                }
+            }
+
+            clamp::<P, D>(&mut x);
+            *wolf = x;
+        }
+
+        evaluate::<P, D>(
+            &population.individuals,
+            &mut population.fitnesses,
+            self.workers,
+        );
+        population.evaluations += population.individuals.len();
+
+        for i in 0..population.individuals.len() {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            update_leaders(&mut population.parameters, &x, f);
+            population.update_best(&x, f);
+        }
+
+        population.iterations += 1;
+    }
+}
+
+// inserts `x` among the leaders if it beats one of them
+fn update_leaders<const D: usize>(leaders: &mut [f64], x: &[f64; D], fitness: f64) {
+    let Some(rank) = leaders
+        .chunks_exact(D + 1)
+        .position(|leader| fitness < leader[0])
+    else {
+        return;
+    };
+
+    let start = rank * (D + 1);
+    leaders.copy_within(start..leaders.len() - (D + 1), start + D + 1);
+    leaders[start] = fitness;
+    leaders[start + 1..start + D + 1].copy_from_slice(x);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn grey_wolf_optimizer_works() {
+        let solve = |workers| {
+            grey_wolf_optimizer::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(10_000),
+                &GreyWolfOptimizer {
+                    population_size: 20,
+                    workers,
+                },
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-10, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+
+    #[test]
+    fn leaders_stay_sorted() {
+        let mut leaders = vec![f64::INFINITY; 3 * 3];
+        for (x, f) in [([1.0, 1.0], 4.0), ([2.0, 2.0], 2.0), ([3.0, 3.0], 5.0)] {
+            update_leaders(&mut leaders, &x, f);
+        }
+        update_leaders(&mut leaders, &[4.0, 4.0], 3.0);
+        update_leaders(&mut leaders, &[5.0, 5.0], 6.0);
+
+        assert_eq!(leaders, [2.0, 2.0, 2.0, 3.0, 4.0, 4.0, 4.0, 1.0, 1.0]);
+    }
+}
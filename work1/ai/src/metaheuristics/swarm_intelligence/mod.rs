@@ -0,0 +1,96 @@
+use rand::distributions::{Distribution, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::Problem;
+
+mod artificial_bee_colony;
+mod bat_algorithm;
+mod continuous_ant_colony;
+mod cuckoo_search;
+mod firefly_algorithm;
+mod grey_wolf_optimizer;
+mod particle_swarm;
+mod whale_optimization_algorithm;
+
+pub use artificial_bee_colony::{
+    artificial_bee_colony, constrained_artificial_bee_colony, ArtificialBeeColony,
+    ConstrainedArtificialBeeColony,
+};
+pub use bat_algorithm::{bat_algorithm, BatAlgorithm};
+pub use continuous_ant_colony::{continuous_ant_colony, ContinuousAntColony};
+pub use cuckoo_search::{cuckoo_search, CuckooSearch};
+pub use firefly_algorithm::{firefly_algorithm, FireflyAlgorithm};
+pub use grey_wolf_optimizer::{grey_wolf_optimizer, GreyWolfOptimizer};
+pub use particle_swarm::{particle_swarm, Neighborhood, ParticleSwarm, VelocityUpdate};
+pub use whale_optimization_algorithm::{whale_optimization_algorithm, WhaleOptimizationAlgorithm};
+
+// index drawn with probability proportional to its weight
+fn roulette<R>(rng: &mut R, weights: &[f64]) -> usize
+where
+    R: Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+{
+    let total = weights.iter().sum::<f64>();
+    let mut u = rng.sample::<f64, _>(&UniformClosedOpen01) * total;
+
+    for (i, &weight) in weights.iter().enumerate() {
+        if u < weight {
+            return i;
+        }
+        u -= weight;
+    }
+    weights.len() - 1
+}
+
+// moves every variable of `x` back inside its range
+fn clamp<P, const D: usize>(x: &mut [f64; D])
+where
+    P: Problem<D>,
+{
+    for (xj, range) in x.iter_mut().zip(&P::RANGES) {
+        *xj = xj.clamp(range.start, range.end);
+    }
+}
+
+// fitness of a food source, which grows as its objective value falls
+fn quality(f: f64) -> f64 {
+    if f >= 0.0 {
+        1.0 / (1.0 + f)
+    } else {
+        1.0 + f.abs()
+    }
+}
+
+// Deb's feasibility rules: a feasible point beats an infeasible one, two
+// feasible points are compared by objective value and two infeasible ones by
+// violation
+fn deb_better(f: f64, violation: f64, other_f: f64, other_violation: f64) -> bool {
+    if violation == 0.0 && other_violation == 0.0 {
+        f < other_f
+    } else {
+        violation < other_violation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use crate::metaheuristics::observer::Observer;
+    use crate::metaheuristics::tests::Sphere;
+    use crate::metaheuristics::{Population, Problem};
+
+    // checks after every iteration that the whole population lies inside the
+    // ranges of the sphere
+    pub(super) struct InRanges;
+
+    impl Observer<4> for InRanges {
+        fn generation(&mut self, population: &Population<4>) -> ControlFlow<()> {
+            for x in &population.individuals {
+                for (xj, range) in x.iter().zip(&Sphere::RANGES) {
+                    assert!((range.start..=range.end).contains(xj), "{x:?}");
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+}
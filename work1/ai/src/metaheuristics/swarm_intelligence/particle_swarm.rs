@@ -0,0 +1,648 @@
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// How a particle updates its velocity from its personal best `p`, the best
+/// personal best `l` of its neighborhood, and uniform random numbers `r`
+/// drawn for every variable.
+#[derive(Clone, Copy)]
+pub enum VelocityUpdate {
+    /// `inertia v + cognitive r1 (p - x) + social r2 (l - x)`, the original
+    /// particle swarm with an inertia of 1.
+    Inertia {
+        inertia: f64,
+        cognitive: f64,
+        social: f64,
+    },
+    /// Clerc's constriction, `χ (v + cognitive r1 (p - x) + social r2 (l - x))`
+    /// with `χ` chosen from `cognitive + social`, which must exceed 4, so that
+    /// the swarm converges without a velocity limit.
+    Constriction { cognitive: f64, social: f64 },
+    /// Fully informed particle swarm of Mendes et al., pulled by the personal
+    /// bests `pk` of its whole neighborhood `N` as
+    /// `χ (v + Σ rk acceleration / |N| (pk - x))`, with `χ` chosen from
+    /// `acceleration`, which must also exceed 4 and is usually 4.1.
+    FullyInformed { acceleration: f64 },
+    /// Comprehensive learning particle swarm of Liang et al., which learns
+    /// each variable from an exemplar, `inertia v + acceleration r (pe - x)`.
+    /// The exemplar is the particle itself or, with a probability that grows
+    /// from 0.05 for the first particle to 0.5 for the last, the better of
+    /// two other random particles, and is drawn again once the personal best
+    /// has not improved for `refreshing_gap` iterations. The inertia goes
+    /// linearly from its first value to its second over the budget. The
+    /// neighborhood is not used.
+    ComprehensiveLearning {
+        inertia: (f64, f64),
+        acceleration: f64,
+        refreshing_gap: usize,
+    },
+    /// Standard particle swarm 2011, `inertia v + x' - x` for a point `x'`
+    /// drawn uniformly from the hypersphere around
+    /// `G = x + acceleration (p + l - 2x) / 3` that reaches `x`, which does
+    /// not depend on the rotation of the coordinates. When `l` is the
+    /// particle's own best, `G = x + acceleration (p - x) / 2`. It is meant
+    /// to be used with a random neighborhood of 3 informants, an inertia of
+    /// `1 / (2 ln 2)` and an acceleration of `1/2 + ln 2`.
+    Standard2011 { inertia: f64, acceleration: f64 },
+}
+
+/// Which particles a particle learns from, itself included.
+#[derive(Clone, Copy)]
+pub enum Neighborhood {
+    /// All of them.
+    Global,
+    /// The previous and the next one.
+    Ring,
+    /// The four adjacent ones on a wrapping grid, as square as the size of
+    /// the swarm allows.
+    VonNeumann,
+    /// Every particle informs itself and `informants` random ones, drawn
+    /// again after every iteration that does not improve the best point.
+    Random { informants: usize },
+}
+
+/// Particle swarm optimization. Particles that leave the ranges are moved
+/// back to their bounds and stop along those variables.
+///
+/// The parameters of the population keep, for every particle, its velocity,
+/// its personal best and the fitness there, followed by the indexes of its
+/// exemplars under comprehensive learning or of the particles it informs
+/// under a random neighborhood. The ages count the iterations since the
+/// personal bests last improved.
+pub struct ParticleSwarm {
+    pub population_size: usize,
+    pub update: VelocityUpdate,
+    pub neighborhood: Neighborhood,
+    /// Number of threads evaluating the particles.
+    pub workers: usize,
+}
+
+pub fn particle_swarm<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &ParticleSwarm,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for ParticleSwarm {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let phi = match self.update {
+            VelocityUpdate::Constriction { cognitive, social } => cognitive + social,
+            VelocityUpdate::FullyInformed { acceleration } => acceleration,
+            _ => f64::INFINITY,
+        };
+        assert!(phi > 4.0, "the constriction needs φ > 4, not {phi}");
+
+        let mut population = Population::random::<R, P>(rng, self.population_size, self.workers);
+        let stride = self.stride::<D>();
+
+        // velocities that can reach any point of the ranges
+        population.parameters = vec![0.0; self.population_size * stride];
+        for ((x, &f), particle) in population
+            .individuals
+            .iter()
+            .zip(&population.fitnesses)
+            .zip(population.parameters.chunks_exact_mut(stride))
+        {
+            for (j, range) in P::RANGES.iter().enumerate() {
+                let velocity = UniformClosedOpen::new(range.start - x[j], range.end - x[j]);
+                particle[j] = rng.sample(&velocity);
+            }
+            particle[D..2 * D].copy_from_slice(x);
+            particle[2 * D] = f;
+        }
+
+        match (self.update, self.neighborhood) {
+            (VelocityUpdate::ComprehensiveLearning { .. }, _) => {
+                for i in 0..self.population_size {
+                    self.choose_exemplars::<R, D>(rng, &mut population, i);
+                }
+            }
+            (_, Neighborhood::Random { .. }) => self.draw_informants::<R, D>(rng, &mut population),
+            _ => {}
+        }
+
+        population
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+        let stride = self.stride::<D>();
+        let progress = termination.progress(population.iterations, population.evaluations);
+        let previous_best = population.best_fitness;
+
+        // every particle moves from the personal bests of the previous
+        // iteration
+        let neighborhoods = self.neighborhoods::<D>(population);
+        let bests = population
+            .parameters
+            .chunks_exact(stride)
+            .map(|particle| {
+                let mut best = [0.0; D];
+                best.copy_from_slice(&particle[D..2 * D]);
+                (best, particle[2 * D])
+            })
+            .collect::<Vec<_>>();
+
+        for (i, (x, particle)) in population
+            .individuals
+            .iter_mut()
+            .zip(population.parameters.chunks_exact_mut(stride))
+            .enumerate()
+        {
+            let (velocity, rest) = particle.split_at_mut(D);
+            let p = &bests[i].0;
+
+            let local = || {
+                let l = neighborhoods[i]
+                    .iter()
+                    .copied()
+                    .min_by(|&a, &b| bests[a].1.total_cmp(&bests[b].1))
+                    .unwrap();
+                (l, &bests[l].0)
+            };
+
+            match self.update {
+                VelocityUpdate::Inertia {
+                    inertia,
+                    cognitive,
+                    social,
+                } => {
+                    let (_, l) = local();
+                    for j in 0..D {
+                        let r1 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                        let r2 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                        velocity[j] = inertia * velocity[j]
+                            + cognitive * r1 * (p[j] - x[j])
+                            + social * r2 * (l[j] - x[j]);
+                    }
+                }
+                VelocityUpdate::Constriction { cognitive, social } => {
+                    let chi = constriction(cognitive + social);
+                    let (_, l) = local();
+                    for j in 0..D {
+                        let r1 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                        let r2 = rng.sample::<f64, _>(&UniformClosedOpen01);
+                        velocity[j] = chi
+                            * (velocity[j]
+                                + cognitive * r1 * (p[j] - x[j])
+                                + social * r2 * (l[j] - x[j]));
+                    }
+                }
+                VelocityUpdate::FullyInformed { acceleration } => {
+                    let chi = constriction(acceleration);
+                    let share = acceleration / neighborhoods[i].len() as f64;
+                    for j in 0..D {
+                        let mut pull = 0.0;
+                        for &k in &neighborhoods[i] {
+                            let r = rng.sample::<f64, _>(&UniformClosedOpen01);
+                            pull += r * share * (bests[k].0[j] - x[j]);
+                        }
+                        velocity[j] = chi * (velocity[j] + pull);
+                    }
+                }
+                VelocityUpdate::ComprehensiveLearning {
+                    inertia: (first, last),
+                    acceleration,
+                    ..
+                } => {
+                    let inertia = first + (last - first) * progress;
+                    let exemplars = &rest[D + 1..];
+                    for j in 0..D {
+                        let r = rng.sample::<f64, _>(&UniformClosedOpen01);
+                        let exemplar = &bests[exemplars[j] as usize].0;
+                        velocity[j] =
+                            inertia * velocity[j] + acceleration * r * (exemplar[j] - x[j]);
+                    }
+                }
+                VelocityUpdate::Standard2011 {
+                    inertia,
+                    acceleration,
+                } => {
+                    let (k, l) = local();
+
+                    // the center of gravity of x, of the point around p and,
+                    // unless it is the particle itself, of the one around l
+                    let mut center = [0.0; D];
+                    for j in 0..D {
+                        center[j] = if k == i {
+                            x[j] + acceleration * (p[j] - x[j]) / 2.0
+                        } else {
+                            x[j] + acceleration * (p[j] + l[j] - 2.0 * x[j]) / 3.0
+                        };
+                    }
+
+                    let point = hypersphere(rng, &center, x);
+                    for j in 0..D {
+                        velocity[j] = inertia * velocity[j] + point[j] - x[j];
+                    }
+                }
+            }
+
+            for (j, range) in P::RANGES.iter().enumerate() {
+                x[j] += velocity[j];
+                if x[j] < range.start || x[j] > range.end {
+                    x[j] = x[j].clamp(range.start, range.end);
+                    velocity[j] = 0.0;
+                }
+            }
+        }
+
+        evaluate::<P, D>(
+            &population.individuals,
+            &mut population.fitnesses,
+            self.workers,
+        );
+        population.evaluations += n;
+
+        for i in 0..n {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            let particle = &mut population.parameters[i * stride..(i + 1) * stride];
+
+            if f < particle[2 * D] {
+                particle[D..2 * D].copy_from_slice(&x);
+                particle[2 * D] = f;
+                population.ages[i] = 0;
+            } else {
+                population.ages[i] += 1;
+            }
+
+            population.update_best(&x, f);
+        }
+
+        match (self.update, self.neighborhood) {
+            (VelocityUpdate::ComprehensiveLearning { refreshing_gap, .. }, _) => {
+                for i in 0..n {
+                    if population.ages[i] >= refreshing_gap {
+                        self.choose_exemplars::<R, D>(rng, population, i);
+                        population.ages[i] = 0;
+                    }
+                }
+            }
+            (_, Neighborhood::Random { .. }) if population.best_fitness >= previous_best => {
+                self.draw_informants::<R, D>(rng, population);
+            }
+            _ => {}
+        }
+
+        population.iterations += 1;
+    }
+}
+
+impl ParticleSwarm {
+    // length of the parameters of a particle
+    fn stride<const D: usize>(&self) -> usize {
+        let extra = match (self.update, self.neighborhood) {
+            (VelocityUpdate::ComprehensiveLearning { .. }, _) => D,
+            (_, Neighborhood::Random { informants }) => informants,
+            _ => 0,
+        };
+        2 * D + 1 + extra
+    }
+
+    fn neighborhoods<const D: usize>(&self, population: &Population<D>) -> Vec<Vec<usize>> {
+        let n = population.individuals.len();
+
+        match self.neighborhood {
+            Neighborhood::Global => vec![(0..n).collect(); n],
+            Neighborhood::Ring => (0..n)
+                .map(|i| vec![(i + n - 1) % n, i, (i + 1) % n])
+                .collect(),
+            Neighborhood::VonNeumann => {
+                let columns = (1..=n)
+                    .filter(|&c| n.is_multiple_of(c) && c * c <= n)
+                    .max()
+                    .unwrap_or(1);
+                let rows = n / columns;
+
+                (0..n)
+                    .map(|i| {
+                        let (row, column) = (i / columns, i % columns);
+                        vec![
+                            i,
+                            (row + rows - 1) % rows * columns + column,
+                            (row + 1) % rows * columns + column,
+                            row * columns + (column + columns - 1) % columns,
+                            row * columns + (column + 1) % columns,
+                        ]
+                    })
+                    .collect()
+            }
+            Neighborhood::Random { .. } => {
+                let stride = self.stride::<D>();
+                let mut neighborhoods = (0..n).map(|i| vec![i]).collect::<Vec<_>>();
+
+                for (j, particle) in population.parameters.chunks_exact(stride).enumerate() {
+                    for &k in &particle[2 * D + 1..] {
+                        neighborhoods[k as usize].push(j);
+                    }
+                }
+                neighborhoods
+            }
+        }
+    }
+
+    fn draw_informants<R, const D: usize>(&self, rng: &mut R, population: &mut Population<D>)
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+    {
+        let n = population.individuals.len();
+        let stride = self.stride::<D>();
+
+        for particle in population.parameters.chunks_exact_mut(stride) {
+            for k in &mut particle[2 * D + 1..] {
+                *k = rng
+                    .sample(&UniformClosedOpen::new(0 as f64, n as f64))
+                    .floor();
+            }
+        }
+    }
+
+    fn choose_exemplars<R, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        i: usize,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>,
+    {
+        let n = population.individuals.len();
+        let stride = self.stride::<D>();
+
+        let share = i as f64 / (n - 1).max(1) as f64;
+        let learning_probability =
+            0.05 + 0.45 * (f64::exp(10.0 * share) - 1.0) / (f64::exp(10.0) - 1.0);
+
+        let fitness = |k: usize| population.parameters[k * stride + 2 * D];
+        let mut exemplars = [i; D];
+        if n > 1 {
+            let tournament = |rng: &mut R| {
+                let (a, b) = (other(rng, n, i), other(rng, n, i));
+                if fitness(a) <= fitness(b) {
+                    a
+                } else {
+                    b
+                }
+            };
+
+            for exemplar in &mut exemplars {
+                if rng.sample::<f64, _>(&UniformClosedOpen01) < learning_probability {
+                    *exemplar = tournament(rng);
+                }
+            }
+
+            // at least one variable is learnt from another particle
+            if exemplars.iter().all(|&k| k == i) {
+                let j = rng.sample(&UniformClosedOpen::new(0 as f64, D as f64)) as usize;
+                exemplars[j] = tournament(rng);
+            }
+        }
+
+        let particle = &mut population.parameters[i * stride..(i + 1) * stride];
+        for (k, &exemplar) in particle[2 * D + 1..].iter_mut().zip(&exemplars) {
+            *k = exemplar as f64;
+        }
+    }
+}
+
+// constriction coefficient of Clerc for φ > 4
+fn constriction(phi: f64) -> f64 {
+    2.0 / (phi - 2.0 + f64::sqrt(phi * phi - 4.0 * phi))
+}
+
+// a random particle other than `i`
+fn other<R>(rng: &mut R, n: usize, i: usize) -> usize
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+{
+    let k = rng.sample(&UniformClosedOpen::new(0 as f64, (n - 1) as f64)) as usize;
+    if k >= i {
+        k + 1
+    } else {
+        k
+    }
+}
+
+// uniform in the ball centered on `center` that reaches `x`
+fn hypersphere<R, const D: usize>(rng: &mut R, center: &[f64; D], x: &[f64; D]) -> [f64; D]
+where
+    R: Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+{
+    let radius = center
+        .iter()
+        .zip(x)
+        .map(|(c, xj)| (c - xj) * (c - xj))
+        .sum::<f64>()
+        .sqrt();
+
+    let mut direction = [0.0; D];
+    for dj in &mut direction {
+        *dj = rng.sample(&Normal::new(0.0, 1.0));
+    }
+    let norm = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+
+    let length = radius
+        * rng
+            .sample::<f64, _>(&UniformClosedOpen01)
+            .powf(1.0 / D as f64);
+
+    let mut point = *center;
+    if norm > 0.0 {
+        for (pj, dj) in point.iter_mut().zip(&direction) {
+            *pj += length * dj / norm;
+        }
+    }
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    fn swarm(
+        population_size: usize,
+        update: VelocityUpdate,
+        neighborhood: Neighborhood,
+    ) -> ParticleSwarm {
+        ParticleSwarm {
+            population_size,
+            update,
+            neighborhood,
+            workers: 1,
+        }
+    }
+
+    #[test]
+    fn particle_swarms_work() {
+        let variants = [
+            (
+                VelocityUpdate::Inertia {
+                    inertia: 0.7298,
+                    cognitive: 1.49618,
+                    social: 1.49618,
+                },
+                Neighborhood::Global,
+            ),
+            (
+                VelocityUpdate::Constriction {
+                    cognitive: 2.05,
+                    social: 2.05,
+                },
+                Neighborhood::Ring,
+            ),
+            (
+                VelocityUpdate::FullyInformed { acceleration: 4.1 },
+                Neighborhood::VonNeumann,
+            ),
+            (
+                VelocityUpdate::ComprehensiveLearning {
+                    inertia: (0.9, 0.4),
+                    acceleration: 1.49445,
+                    refreshing_gap: 7,
+                },
+                Neighborhood::Global,
+            ),
+            (
+                VelocityUpdate::Standard2011 {
+                    inertia: 1.0 / (2.0 * f64::ln(2.0)),
+                    acceleration: 0.5 + f64::ln(2.0),
+                },
+                Neighborhood::Random { informants: 3 },
+            ),
+        ];
+
+        for (update, neighborhood) in variants {
+            let solve = |workers| {
+                particle_swarm::<_, Sphere, _, 4>(
+                    &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                    Termination::evaluations(20_000),
+                    &ParticleSwarm {
+                        workers,
+                        ..swarm(20, update, neighborhood)
+                    },
+                    &mut InRanges,
+                )
+            };
+
+            let solution = solve(1);
+            assert!(solution.fitness < 1e-6, "{solution:?}");
+            assert_eq!(solution, solve(3));
+        }
+    }
+
+    #[test]
+    fn constriction_works() {
+        assert!((constriction(4.1) - 0.7298437881283576).abs() < 1e-15);
+        assert!((constriction(5.0) - 2.0 / (3.0 + f64::sqrt(5.0))).abs() < 1e-15);
+
+        // χ falls as φ grows past 4, where it is 1
+        assert!((constriction(4.0 + 1e-12) - 1.0).abs() < 1e-5);
+        assert!(constriction(4.5) > constriction(6.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the constriction needs φ > 4")]
+    fn constriction_needs_phi_above_4() {
+        let swarm = swarm(
+            10,
+            VelocityUpdate::Constriction {
+                cognitive: 2.0,
+                social: 2.0,
+            },
+            Neighborhood::Global,
+        );
+        swarm.initialize::<_, Sphere, 4>(&mut Xoshiro256Plus::new([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn neighborhoods_work() {
+        let update = VelocityUpdate::Inertia {
+            inertia: 0.7,
+            cognitive: 1.5,
+            social: 1.5,
+        };
+        let informants = |size, neighborhood| {
+            let swarm = swarm(size, update, neighborhood);
+            let population =
+                swarm.initialize::<_, Sphere, 4>(&mut Xoshiro256Plus::new([1, 2, 3, 4]));
+            (swarm.neighborhoods::<4>(&population), population)
+        };
+
+        let (global, _) = informants(4, Neighborhood::Global);
+        assert_eq!(global, vec![vec![0, 1, 2, 3]; 4]);
+
+        let (ring, _) = informants(5, Neighborhood::Ring);
+        assert_eq!(ring[0], [4, 0, 1]);
+        assert_eq!(ring[2], [1, 2, 3]);
+        assert_eq!(ring[4], [3, 4, 0]);
+
+        // 12 particles make a grid of 4 rows and 3 columns, listed as the
+        // particle, up, down, left and right
+        let (grid, _) = informants(12, Neighborhood::VonNeumann);
+        assert_eq!(grid[0], [0, 9, 3, 2, 1]);
+        assert_eq!(grid[4], [4, 1, 7, 3, 5]);
+        assert_eq!(grid[11], [11, 8, 2, 10, 9]);
+
+        // a prime size leaves a single column
+        let (column, _) = informants(7, Neighborhood::VonNeumann);
+        assert_eq!(column[3], [3, 2, 4, 3, 3]);
+
+        // every particle hears from itself and from those that inform it
+        let (random, population) = informants(10, Neighborhood::Random { informants: 3 });
+        let stride = 2 * 4 + 1 + 3;
+        for (i, neighborhood) in random.iter().enumerate() {
+            assert_eq!(neighborhood[0], i);
+
+            let mut expected = population
+                .parameters
+                .chunks_exact(stride)
+                .enumerate()
+                .flat_map(|(j, particle)| {
+                    particle[2 * 4 + 1..]
+                        .iter()
+                        .filter(move |&&k| k as usize == i)
+                        .map(move |_| j)
+                })
+                .collect::<Vec<_>>();
+            expected.insert(0, i);
+            assert_eq!(*neighborhood, expected);
+        }
+        let informed = random.iter().map(|n| n.len() - 1).sum::<usize>();
+        assert_eq!(informed, 10 * 3);
+    }
+}
@@ -0,0 +1,141 @@
+use std::f64::consts::TAU;
+
+use rand::distributions::{Distribution, Normal, UniformClosedOpen, UniformClosedOpen01};
+use rand::rngs::Rng;
+
+use super::clamp;
+use crate::metaheuristics::observer::Observer;
+use crate::metaheuristics::{
+    evaluate, run, Metaheuristic, Population, Problem, Solution, Termination,
+};
+
+/// Whale optimization algorithm of Mirjalili and Lewis. Each whale either
+/// follows a logarithmic spiral around the best point, as in bubble-net
+/// feeding, or moves along a shrinking or widening line towards the best
+/// point or, while `|A| >= 1`, towards a random whale to search for prey. As
+/// in the grey wolf optimizer, `A` is scaled by `a`, which decreases linearly
+/// from 2 to 0 over the budget.
+pub struct WhaleOptimizationAlgorithm {
+    pub population_size: usize,
+    /// Constant `b` of the spiral `e^(bl) cos(2πl)`, usually 1.
+    pub spiral_shape: f64,
+    /// Number of threads evaluating the whales.
+    pub workers: usize,
+}
+
+pub fn whale_optimization_algorithm<R, P, O, const D: usize>(
+    rng: &mut R,
+    termination: Termination,
+    parameters: &WhaleOptimizationAlgorithm,
+    observer: &mut O,
+) -> Solution<D>
+where
+    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+        + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+        + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+    P: Problem<D>,
+    O: Observer<D>,
+{
+    let population = parameters.initialize::<R, P, D>(rng);
+    run::<R, P, _, O, D>(rng, termination, parameters, population, observer)
+}
+
+impl Metaheuristic for WhaleOptimizationAlgorithm {
+    fn initialize<R, P, const D: usize>(&self, rng: &mut R) -> Population<D>
+    where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        Population::random::<R, P>(rng, self.population_size, self.workers)
+    }
+
+    fn iterate<R, P, const D: usize>(
+        &self,
+        rng: &mut R,
+        population: &mut Population<D>,
+        termination: &Termination,
+    ) where
+        R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>
+            + Rng<<UniformClosedOpen01 as Distribution<f64>>::Backend>
+            + Rng<<Normal<f64> as Distribution<f64>>::Backend>,
+        P: Problem<D>,
+    {
+        let n = population.individuals.len();
+        let a = 2.0 * (1.0 - termination.progress(population.iterations, population.evaluations));
+        let best = population.best_individual;
+
+        // every whale moves from the positions of the previous iteration
+        let whales = population.individuals.clone();
+
+        for x in &mut population.individuals {
+            let big_a = 2.0 * a * rng.sample::<f64, _>(&UniformClosedOpen01) - a;
+            let c = 2.0 * rng.sample::<f64, _>(&UniformClosedOpen01);
+
+            if rng.sample::<f64, _>(&UniformClosedOpen01) < 0.5 {
+                let prey = if big_a.abs() < 1.0 {
+                    best
+                } else {
+                    whales[rng.sample(&UniformClosedOpen::new(0 as f64, n as f64)) as usize]
+                };
+
+                for (xj, &pj) in x.iter_mut().zip(&prey) {
+                    let distance = (c * pj - *xj).abs();
+                    *xj = pj - big_a * distance;
+                }
+            } else {
+                let l: f64 = rng.sample(&UniformClosedOpen::new(-1.0, 1.0));
+                let spiral = (self.spiral_shape * l).exp() * (TAU * l).cos();
+
+                for (xj, &bj) in x.iter_mut().zip(&best) {
+                    *xj = (bj - *xj).abs() * spiral + bj;
+                }
+            }
+
+            clamp::<P, D>(x);
+        }
+
+        evaluate::<P, D>(
+            &population.individuals,
+            &mut population.fitnesses,
+            self.workers,
+        );
+        population.evaluations += n;
+
+        for i in 0..n {
+            let (x, f) = (population.individuals[i], population.fitnesses[i]);
+            population.update_best(&x, f);
+        }
+
+        population.iterations += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metaheuristics::swarm_intelligence::tests::InRanges;
+    use crate::metaheuristics::tests::Sphere;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn whale_optimization_algorithm_works() {
+        let solve = |workers| {
+            whale_optimization_algorithm::<_, Sphere, _, 4>(
+                &mut Xoshiro256Plus::new([1, 2, 3, 4]),
+                Termination::evaluations(10_000),
+                &WhaleOptimizationAlgorithm {
+                    population_size: 20,
+                    spiral_shape: 1.0,
+                    workers,
+                },
+                &mut InRanges,
+            )
+        };
+
+        let solution = solve(1);
+        assert!(solution.fitness < 1e-10, "{solution:?}");
+        assert_eq!(solution, solve(3));
+    }
+}
@@ -0,0 +1,59 @@
+//! The error shared by the readers of line-oriented text files, which
+//! report where the text stops being valid.
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The content of the given line, counted from 1, is not valid.
+    Parse {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Error {
+    pub fn at(line: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Parse { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Checks that every text fails to parse at the line it is paired with.
+#[cfg(test)]
+pub(crate) fn assert_error_lines<T, S>(
+    cases: impl IntoIterator<Item = (S, usize)>,
+    parse: impl Fn(&str) -> Result<T, Error>,
+) where
+    T: fmt::Debug,
+    S: AsRef<str>,
+{
+    for (text, expected) in cases {
+        let text = text.as_ref();
+        match parse(text) {
+            Err(Error::Parse { line, .. }) => assert_eq!(line, expected, "{text:?}"),
+            other => panic!("{text:?} parsed as {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+//! Dependency free SVG plots of experiment results.
+
+use std::io::{self, Write};
+
+use crate::stats::quantile;
+
+/// Convergence histories of the runs of one algorithm, each one a list of
+/// `(evaluations, best_fitness)` steps as recorded by
+/// [`History`](crate::metaheuristics::observer::History).
+pub struct Series {
+    pub label: String,
+    pub runs: Vec<Vec<(usize, f64)>>,
+}
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 500.0;
+
+const LEFT: f64 = 90.0;
+const RIGHT: f64 = 20.0;
+const TOP: f64 = 40.0;
+const BOTTOM: f64 = 60.0;
+
+// number of evaluation counts at which the runs are compared
+const SAMPLES: usize = 200;
+
+// the default matplotlib cycle, so plots match the ones made from python
+const COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// Draws the median best value so far against the number of evaluations for
+/// every series, with a band between the first and third quartiles of its
+/// runs. Both axes are logarithmic, so values that are not positive are
+/// clamped to the smallest positive one, and values that are not finite are
+/// left out.
+pub fn convergence<W: Write>(mut writer: W, title: &str, series: &[Series]) -> io::Result<()> {
+    let runs = || {
+        series
+            .iter()
+            .flat_map(|s| &s.runs)
+            .filter(|run| !run.is_empty())
+    };
+
+    let x_min = runs()
+        .map(|run| run[0].0.max(1) as f64)
+        .fold(f64::INFINITY, f64::min);
+    let x_max = runs()
+        .map(|run| run[run.len() - 1].0 as f64)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    if !x_min.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there are no runs to plot",
+        ));
+    }
+    let x_max = x_max.max(x_min * 10.0);
+
+    let grid = (0..SAMPLES)
+        .map(|i| x_min * (x_max / x_min).powf(i as f64 / (SAMPLES - 1) as f64))
+        .collect::<Vec<_>>();
+
+    // (x, first quartile, median, third quartile) of every series
+    let bands = series
+        .iter()
+        .map(|s| {
+            grid.iter()
+                .filter_map(|&x| {
+                    let mut values = s
+                        .runs
+                        .iter()
+                        .filter_map(|run| best_at(run, x))
+                        .filter(|y| y.is_finite())
+                        .collect::<Vec<_>>();
+                    if values.is_empty() {
+                        return None;
+                    }
+                    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+                    Some((
+                        x,
+                        quantile(&values, 0.25),
+                        quantile(&values, 0.5),
+                        quantile(&values, 0.75),
+                    ))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let values = || bands.iter().flatten().flat_map(|&(_, a, b, c)| [a, b, c]);
+
+    let tiny = values().filter(|&y| y > 0.0).fold(f64::INFINITY, f64::min);
+    let tiny = if tiny.is_finite() { tiny } else { 1.0 };
+
+    let y_min = values().map(|y| y.max(tiny)).fold(f64::INFINITY, f64::min);
+    let y_max = values()
+        .map(|y| y.max(tiny))
+        .fold(f64::NEG_INFINITY, f64::max);
+    let (y_min, y_max) = if y_min < y_max {
+        (y_min, y_max)
+    } else {
+        (tiny / 2.0, tiny * 2.0)
+    };
+
+    let x_axis = Axis::new(x_min, x_max, LEFT, WIDTH - RIGHT);
+    let y_axis = Axis::new(y_min, y_max, HEIGHT - BOTTOM, TOP);
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="12">"#
+    )?;
+    writeln!(writer, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+    writeln!(
+        writer,
+        r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>"#,
+        WIDTH / 2.0,
+        TOP / 2.0 + 6.0,
+        escape(title)
+    )?;
+
+    // grid and tick labels
+    for tick in ticks(x_min, x_max) {
+        let x = x_axis.map(tick);
+        writeln!(
+            writer,
+            r##"<line x1="{x:.2}" y1="{TOP}" x2="{x:.2}" y2="{}" stroke="#dddddd"/>"##,
+            HEIGHT - BOTTOM
+        )?;
+        writeln!(
+            writer,
+            r#"<text x="{x:.2}" y="{}" text-anchor="middle">{}</text>"#,
+            HEIGHT - BOTTOM + 18.0,
+            label(tick)
+        )?;
+    }
+    for tick in ticks(y_min, y_max) {
+        let y = y_axis.map(tick);
+        writeln!(
+            writer,
+            r##"<line x1="{LEFT}" y1="{y:.2}" x2="{}" y2="{y:.2}" stroke="#dddddd"/>"##,
+            WIDTH - RIGHT
+        )?;
+        writeln!(
+            writer,
+            r#"<text x="{}" y="{:.2}" text-anchor="end">{}</text>"#,
+            LEFT - 6.0,
+            y + 4.0,
+            label(tick)
+        )?;
+    }
+
+    writeln!(
+        writer,
+        r#"<rect x="{LEFT}" y="{TOP}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+        WIDTH - LEFT - RIGHT,
+        HEIGHT - TOP - BOTTOM
+    )?;
+    writeln!(
+        writer,
+        r#"<text x="{}" y="{}" text-anchor="middle">evaluations</text>"#,
+        (LEFT + WIDTH - RIGHT) / 2.0,
+        HEIGHT - 15.0
+    )?;
+    writeln!(
+        writer,
+        r#"<text transform="translate(20 {}) rotate(-90)" text-anchor="middle">best fitness</text>"#,
+        (TOP + HEIGHT - BOTTOM) / 2.0
+    )?;
+
+    for (i, band) in bands.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        let point = |x: f64, y: f64| format!("{:.2},{:.2}", x_axis.map(x), y_axis.map(y.max(tiny)));
+
+        let outline = band
+            .iter()
+            .map(|&(x, _, _, q3)| point(x, q3))
+            .chain(band.iter().rev().map(|&(x, q1, _, _)| point(x, q1)))
+            .collect::<Vec<_>>();
+        writeln!(
+            writer,
+            r#"<polygon points="{}" fill="{color}" fill-opacity="0.2" stroke="none"/>"#,
+            outline.join(" ")
+        )?;
+
+        let median = band
+            .iter()
+            .map(|&(x, _, median, _)| point(x, median))
+            .collect::<Vec<_>>();
+        writeln!(
+            writer,
+            r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="1.5"/>"#,
+            median.join(" ")
+        )?;
+    }
+
+    // legend, in the top right corner
+    for (i, s) in series.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        let y = TOP + 16.0 + 18.0 * i as f64;
+        let x = WIDTH - RIGHT - 170.0;
+
+        writeln!(
+            writer,
+            r#"<line x1="{x}" y1="{y}" x2="{}" y2="{y}" stroke="{color}" stroke-width="3"/>"#,
+            x + 20.0
+        )?;
+        writeln!(
+            writer,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            x + 26.0,
+            y + 4.0,
+            escape(&s.label)
+        )?;
+    }
+
+    writeln!(writer, "</svg>")?;
+
+    writer.flush()
+}
+
+// best value so far of a run after `evaluations`, if it had started by then
+fn best_at(run: &[(usize, f64)], evaluations: f64) -> Option<f64> {
+    let steps = run.partition_point(|&(e, _)| e as f64 <= evaluations);
+    steps.checked_sub(1).map(|i| run[i].1)
+}
+
+// logarithmic mapping of [min, max] onto [start, end] pixels
+struct Axis {
+    log_min: f64,
+    log_max: f64,
+    start: f64,
+    end: f64,
+}
+
+impl Axis {
+    fn new(min: f64, max: f64, start: f64, end: f64) -> Self {
+        Self {
+            log_min: min.log10(),
+            log_max: max.log10(),
+            start,
+            end,
+        }
+    }
+
+    fn map(&self, value: f64) -> f64 {
+        let t = (value.log10() - self.log_min) / (self.log_max - self.log_min);
+        self.start + t * (self.end - self.start)
+    }
+}
+
+// powers of ten, or 1, 2 and 5 times them when the range spans few decades
+fn ticks(min: f64, max: f64) -> Vec<f64> {
+    let first = min.log10().floor() as i32;
+    let last = max.log10().ceil() as i32;
+
+    let mantissas: &[f64] = match last - first {
+        ..=1 => &[1.0, 1.5, 2.0, 3.0, 5.0, 7.0],
+        2..=3 => &[1.0, 2.0, 5.0],
+        _ => &[1.0],
+    };
+    let step = usize::max(((last - first) / 10) as usize, 1);
+
+    (first..=last)
+        .step_by(step)
+        .flat_map(|exponent| mantissas.iter().map(move |&m| (m, exponent)))
+        .map(|(m, exponent)| m * 10f64.powi(exponent))
+        .filter(|&tick| tick >= min * (1.0 - 1e-9) && tick <= max * (1.0 + 1e-9))
+        .collect()
+}
+
+fn label(tick: f64) -> String {
+    let exponent = tick.log10().floor() as i32;
+
+    if (-3..5).contains(&exponent) {
+        let decimals = (1 - exponent).clamp(0, 4) as usize;
+        let text = format!("{tick:.decimals$}");
+        if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            text
+        }
+    } else {
+        let mantissa = tick / 10f64.powi(exponent);
+        format!("{}e{exponent}", (mantissa * 10.0).round() / 10.0)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plot(series: &[Series]) -> String {
+        let mut svg = Vec::new();
+        convergence(&mut svg, "a <b> & c", series).unwrap();
+        String::from_utf8(svg).unwrap()
+    }
+
+    #[test]
+    fn convergence_works() {
+        let svg = plot(&[Series {
+            label: "GA".to_string(),
+            runs: vec![vec![(1, 100.0), (10, 1.0)]; 3],
+        }]);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">a &lt;b&gt; &amp; c</text>"));
+        assert!(svg.contains(">GA</text>"));
+
+        // the median runs from the top left corner to the bottom right one
+        let median = svg.lines().find(|l| l.starts_with("<polyline")).unwrap();
+        assert!(median.contains(r#"points="90.00,40.00 "#));
+        assert!(median.contains(r#" 780.00,440.00""#));
+    }
+
+    #[test]
+    fn values_that_are_not_positive_are_clamped() {
+        let svg = plot(&[Series {
+            label: "DE".to_string(),
+            runs: vec![vec![(1, 100.0), (10, 1.0), (50, 0.0), (100, -1.0)]],
+        }]);
+
+        // 1 is the smallest positive value, so it is the bottom of the plot
+        let median = svg.lines().find(|l| l.starts_with("<polyline")).unwrap();
+        assert!(median.contains(r#" 780.00,440.00""#));
+        assert!(!svg.contains("NaN") && !svg.contains("inf"));
+
+        // a single value that is not positive still gives a valid plot
+        let svg = plot(&[Series {
+            label: "DE".to_string(),
+            runs: vec![vec![(1, 0.0)]],
+        }]);
+        assert!(!svg.contains("NaN") && !svg.contains("inf"));
+    }
+
+    #[test]
+    fn no_runs_are_an_error() {
+        let series = [Series {
+            label: "GA".to_string(),
+            runs: vec![Vec::new()],
+        }];
+        assert!(convergence(Vec::new(), "", &series).is_err());
+    }
+
+    #[test]
+    fn ticks_work() {
+        assert_eq!(
+            ticks(1.0, 1000.0),
+            [1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0]
+        );
+        assert_eq!(
+            ticks(1e-3, 1e5),
+            [1e-3, 1e-2, 1e-1, 1.0, 10.0, 100.0, 1e3, 1e4, 1e5]
+        );
+
+        assert_eq!(label(0.05), "0.05");
+        assert_eq!(label(2000.0), "2000");
+        assert_eq!(label(1e5), "1e5");
+        assert_eq!(label(2e-6), "2e-6");
+    }
+}
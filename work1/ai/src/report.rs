@@ -0,0 +1,407 @@
+//! Tables of results for papers, in LaTeX with booktabs and in Markdown.
+
+use std::io::{self, Write};
+
+use crate::stats::{Outcome, SignificanceTable, Summary};
+
+/// What the cells of a [`Report`] show, which also decides the best entry of
+/// every problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Statistic {
+    /// Mean and sample standard deviation.
+    Mean,
+    /// Median and interquartile range.
+    Median,
+}
+
+/// Summaries of the runs of every algorithm on every problem, with the lowest
+/// entry of every problem in bold and the outcome of the comparison of every
+/// other algorithm with a baseline marked as `+`, `≈` or `-`. Algorithms run
+/// with different parameters are told apart by their names only, such as
+/// `"GA (pm = 0.1)"`. Cells of algorithms with failed runs, whose values are
+/// NaN, are followed by the number of failures, and are never in bold.
+pub struct Report {
+    pub statistic: Statistic,
+    /// `summaries[p][a]` of algorithm `a` on problem `p`.
+    pub summaries: Vec<Vec<Summary>>,
+    pub significance: SignificanceTable,
+}
+
+impl Report {
+    /// Builds the report from the final values of the runs, `results[p][a]`
+    /// holding those of algorithm `a` on problem `p`, as in
+    /// [`SignificanceTable::new`].
+    pub fn new(
+        algorithms: &[&str],
+        problems: &[&str],
+        results: &[Vec<Vec<f64>>],
+        baseline: usize,
+        alpha: f64,
+        statistic: Statistic,
+    ) -> Self {
+        let significance = SignificanceTable::new(algorithms, problems, results, baseline, alpha);
+
+        let summaries = results
+            .iter()
+            .map(|row| row.iter().map(|runs| Summary::new(runs)).collect())
+            .collect();
+
+        Self {
+            statistic,
+            summaries,
+            significance,
+        }
+    }
+
+    // value and spread shown for a summary
+    fn entry(&self, summary: &Summary) -> (f64, f64) {
+        match self.statistic {
+            Statistic::Mean => (summary.mean, summary.std_dev),
+            Statistic::Median => (summary.median, summary.iqr()),
+        }
+    }
+
+    /// Whether algorithm `a` has the lowest entry on problem `p`, together with
+    /// any algorithm tied with it, among the algorithms without failed runs.
+    pub fn is_best(&self, p: usize, a: usize) -> bool {
+        let value = |s| self.entry(s).0;
+
+        let best = self.summaries[p]
+            .iter()
+            .filter(|s| s.nan == 0)
+            .map(value)
+            .filter(|x| !x.is_nan())
+            .fold(f64::INFINITY, f64::min);
+
+        self.summaries[p][a].nan == 0 && value(&self.summaries[p][a]) == best
+    }
+
+    // number of failed runs out of all of them, if any failed
+    fn failures(&self, p: usize, a: usize) -> Option<String> {
+        let summary = &self.summaries[p][a];
+        (summary.nan > 0).then(|| format!("{}/{} failed", summary.nan, summary.count))
+    }
+
+    fn header(&self) -> Vec<String> {
+        let table = &self.significance;
+
+        table
+            .algorithms
+            .iter()
+            .enumerate()
+            .map(|(a, algorithm)| {
+                if a == table.baseline {
+                    format!("{algorithm} (baseline)")
+                } else {
+                    algorithm.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn outcome(&self, p: usize, a: usize) -> Option<Outcome> {
+        self.significance.comparisons[p][a].map(|c| c.outcome)
+    }
+
+    /// Writes a `table` environment holding a `booktabs` tabular, with a
+    /// column per algorithm and a row per problem, followed by the number of
+    /// problems on which every algorithm is better than, tied with and worse
+    /// than the baseline.
+    pub fn write_latex<W: Write>(
+        &self,
+        mut writer: W,
+        caption: &str,
+        label: &str,
+    ) -> io::Result<()> {
+        let table = &self.significance;
+        let columns = table.algorithms.len();
+
+        writeln!(writer, r"\begin{{table}}[htbp]")?;
+        writeln!(writer, r"  \centering")?;
+        writeln!(writer, r"  \caption{{{}}}", latex_escape(caption))?;
+        writeln!(writer, r"  \label{{{label}}}")?;
+        writeln!(writer, r"  \begin{{tabular}}{{l{}}}", "r".repeat(columns))?;
+        writeln!(writer, r"    \toprule")?;
+
+        let header = self
+            .header()
+            .iter()
+            .map(|h| latex_escape(h))
+            .collect::<Vec<_>>();
+        writeln!(writer, r"    Problem & {} \\", header.join(" & "))?;
+        writeln!(writer, r"    \midrule")?;
+
+        for (p, problem) in table.problems.iter().enumerate() {
+            let cells = (0..columns)
+                .map(|a| {
+                    let (value, spread) = self.entry(&self.summaries[p][a]);
+
+                    let mut value = latex_number(value);
+                    if self.is_best(p, a) {
+                        value = format!(r"\mathbf{{{value}}}");
+                    }
+
+                    let spread = latex_number(spread);
+                    let cell = match self.statistic {
+                        Statistic::Mean => format!(r"{value} \pm {spread}"),
+                        Statistic::Median => format!(r"{value}\ ({spread})"),
+                    };
+
+                    let mark = match self.outcome(p, a) {
+                        Some(Outcome::Better) => "+",
+                        Some(Outcome::Tie) => r"\approx",
+                        Some(Outcome::Worse) => "-",
+                        None => "",
+                    };
+                    let cell = if mark.is_empty() {
+                        format!("${cell}$")
+                    } else {
+                        format!("${{{cell}}}^{{{mark}}}$")
+                    };
+
+                    match self.failures(p, a) {
+                        Some(failures) => format!("{cell} [{failures}]"),
+                        None => cell,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            writeln!(
+                writer,
+                r"    {} & {} \\",
+                latex_escape(problem),
+                cells.join(" & ")
+            )?;
+        }
+
+        writeln!(writer, r"    \midrule")?;
+        writeln!(
+            writer,
+            r"    $+/\approx/-$ & {} \\",
+            self.scores().join(" & ")
+        )?;
+        writeln!(writer, r"    \bottomrule")?;
+        writeln!(writer, r"  \end{{tabular}}")?;
+        writeln!(writer, r"\end{{table}}")
+    }
+
+    /// Writes the same table as [`write_latex`](Self::write_latex) in
+    /// GitHub flavoured Markdown.
+    pub fn write_markdown<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let table = &self.significance;
+        let columns = table.algorithms.len();
+
+        let header = self
+            .header()
+            .iter()
+            .map(|h| markdown_escape(h))
+            .collect::<Vec<_>>();
+        writeln!(writer, "| Problem | {} |", header.join(" | "))?;
+        writeln!(writer, "|:--|{}", "--:|".repeat(columns))?;
+
+        for (p, problem) in table.problems.iter().enumerate() {
+            let cells = (0..columns)
+                .map(|a| {
+                    let (value, spread) = self.entry(&self.summaries[p][a]);
+
+                    let mut value = markdown_number(value);
+                    if self.is_best(p, a) {
+                        value = format!("**{value}**");
+                    }
+
+                    let spread = markdown_number(spread);
+                    let mut cell = match self.statistic {
+                        Statistic::Mean => format!("{value} ± {spread}"),
+                        Statistic::Median => format!("{value} ({spread})"),
+                    };
+
+                    if let Some(outcome) = self.outcome(p, a) {
+                        cell.push_str(&format!(" {outcome}"));
+                    }
+                    if let Some(failures) = self.failures(p, a) {
+                        cell.push_str(&format!(" [{failures}]"));
+                    }
+
+                    cell
+                })
+                .collect::<Vec<_>>();
+
+            writeln!(
+                writer,
+                "| {} | {} |",
+                markdown_escape(problem),
+                cells.join(" | ")
+            )?;
+        }
+
+        writeln!(writer, "| +/≈/- | {} |", self.scores().join(" | "))
+    }
+
+    fn scores(&self) -> Vec<String> {
+        let table = &self.significance;
+
+        (0..table.algorithms.len())
+            .map(|a| {
+                if a == table.baseline {
+                    String::new()
+                } else {
+                    let (better, tie, worse) = table.score(a);
+                    format!("{better}/{tie}/{worse}")
+                }
+            })
+            .collect()
+    }
+}
+
+// three significant digits, with the exponent of scientific notation for
+// values outside [0.01, 10000)
+fn digits(x: f64) -> (String, Option<i32>) {
+    let scientific = format!("{x:.2e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent = exponent.parse::<i32>().unwrap();
+
+    if x == 0.0 {
+        ("0".to_string(), None)
+    } else if (-2..4).contains(&exponent) {
+        let decimals = (2 - exponent).max(0) as usize;
+        (format!("{x:.decimals$}"), None)
+    } else {
+        (mantissa.to_string(), Some(exponent))
+    }
+}
+
+fn latex_number(x: f64) -> String {
+    if x.is_nan() {
+        return r"\mathrm{NaN}".to_string();
+    } else if x.is_infinite() {
+        return if x > 0.0 { r"\infty" } else { r"-\infty" }.to_string();
+    }
+
+    match digits(x) {
+        (mantissa, Some(exponent)) => format!(r"{mantissa} \times 10^{{{exponent}}}"),
+        (text, None) => text,
+    }
+}
+
+fn markdown_number(x: f64) -> String {
+    if !x.is_finite() {
+        return format!("{x}");
+    }
+
+    match digits(x) {
+        (mantissa, Some(exponent)) => format!("{mantissa}e{exponent}"),
+        (text, None) => text,
+    }
+}
+
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', r"\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(statistic: Statistic) -> Report {
+        let results = vec![
+            vec![
+                (0..10).map(|i| i as f64).collect(),
+                (0..10).map(|i| 1e-5 * i as f64).collect(),
+            ],
+            vec![
+                (0..10).map(|i| 2.0 + i as f64).collect(),
+                (0..10).map(|i| 3.0 + i as f64).collect(),
+            ],
+        ];
+
+        Report::new(
+            &["GA", "DE_1"],
+            &["Sphere", "Rastrigin"],
+            &results,
+            0,
+            0.05,
+            statistic,
+        )
+    }
+
+    #[test]
+    fn markdown_works() {
+        let mut markdown = Vec::new();
+        report(Statistic::Median)
+            .write_markdown(&mut markdown)
+            .unwrap();
+
+        let expected = "\
+| Problem | GA (baseline) | DE_1 |
+|:--|--:|--:|
+| Sphere | 4.50 (4.50) | **4.50e-5** (4.50e-5) + |
+| Rastrigin | **6.50** (4.50) | 7.50 (4.50) ≈ |
+| +/≈/- |  | 1/1/0 |
+";
+        assert_eq!(String::from_utf8(markdown).unwrap(), expected);
+    }
+
+    #[test]
+    fn latex_works() {
+        let mut latex = Vec::new();
+        report(Statistic::Mean)
+            .write_latex(&mut latex, "Results", "tab:results")
+            .unwrap();
+        let latex = String::from_utf8(latex).unwrap();
+
+        assert!(latex.contains(r"    Problem & GA (baseline) & DE\_1 \\"));
+        assert!(latex.contains(
+            r"    Sphere & $4.50 \pm 3.03$ & ${\mathbf{4.50 \times 10^{-5}} \pm 3.03 \times 10^{-5}}^{+}$ \\"
+        ));
+        assert!(latex.contains(r"    $+/\approx/-$ &  & 1/1/0 \\"));
+
+        assert_eq!(latex_number(0.012345), "0.0123");
+        assert_eq!(latex_number(1234.5), "1234");
+        assert_eq!(latex_number(-12345.0), r"-1.23 \times 10^{4}");
+    }
+
+    #[test]
+    fn failed_runs_are_never_best() {
+        let mut failed = vec![f64::NAN; 10];
+        failed[0] = 0.0;
+        let results = vec![vec![(1..=10).map(f64::from).collect(), failed]];
+
+        let report = Report::new(
+            &["GA", "DE"],
+            &["Sphere"],
+            &results,
+            0,
+            0.05,
+            Statistic::Mean,
+        );
+        assert!(report.is_best(0, 0));
+        assert!(!report.is_best(0, 1));
+
+        let mut markdown = Vec::new();
+        report.write_markdown(&mut markdown).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown.contains("| Sphere | **5.50** ± 3.03 | 0 ± NaN - [9/10 failed] |"));
+
+        let mut latex = Vec::new();
+        report.write_latex(&mut latex, "", "").unwrap();
+        let latex = String::from_utf8(latex).unwrap();
+        assert!(latex.contains(r"${0 \pm \mathrm{NaN}}^{-}$ [9/10 failed] \\"));
+    }
+}
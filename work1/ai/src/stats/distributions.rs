@@ -0,0 +1,207 @@
+use std::f64::consts::{PI, SQRT_2};
+
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / SQRT_2)
+}
+
+pub fn normal_sf(z: f64) -> f64 {
+    0.5 * erfc(z / SQRT_2)
+}
+
+pub fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+
+    if x < 2.0 {
+        // erf(x) = 2/√π e^(-x²) Σ 2ⁿ x^(2n+1) / (1·3·…·(2n+1)), all terms positive
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > sum * 1e-17 {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        1.0 - 2.0 / PI.sqrt() * f64::exp(-x * x) * sum
+    } else {
+        // erfc(x) = e^(-x²)/√π / (x + (1/2) / (x + 1 / (x + (3/2) / (x + …))))
+        let mut fraction = x;
+        for n in (1..=200).rev() {
+            fraction = x + (n as f64 / 2.0) / fraction;
+        }
+        f64::exp(-x * x) / PI.sqrt() / fraction
+    }
+}
+
+// Lanczos approximation, g = 7
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // reflection formula
+        return f64::ln(PI / f64::sin(PI * x)) - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+
+    0.5 * f64::ln(2.0 * PI) + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+// regularized upper incomplete gamma function Q(a, x)
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let prefactor = f64::exp(a * x.ln() - x - ln_gamma(a));
+
+    if x < a + 1.0 {
+        // series for P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..1000 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        1.0 - sum * prefactor
+    } else {
+        // continued fraction for Q(a, x), modified Lentz's method
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-16 {
+                break;
+            }
+        }
+        prefactor * h
+    }
+}
+
+/// Probability of a chi-squared variable with `df` degrees of freedom being
+/// greater than `x`.
+pub fn chi_squared_sf(x: f64, df: f64) -> f64 {
+    gamma_q(df / 2.0, x / 2.0)
+}
+
+/// Distribution function of the range of `k` independent standard normal
+/// variables, the studentized range with infinite degrees of freedom.
+pub fn studentized_range_cdf(q: f64, k: usize) -> f64 {
+    if q <= 0.0 {
+        return 0.0;
+    }
+
+    // k ∫ φ(z) (Φ(z) - Φ(z - q))^(k-1) dz, by Simpson's rule
+    let (a, b, n) = (-8.0, 8.0, 2000);
+    let h = (b - a) / n as f64;
+
+    let f = |z: f64| {
+        let density = f64::exp(-z * z / 2.0) / (2.0 * PI).sqrt();
+        density * (normal_cdf(z) - normal_cdf(z - q)).powi(k as i32 - 1)
+    };
+
+    let mut sum = f(a) + f(b);
+    for i in 1..n {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * f(a + i as f64 * h);
+    }
+
+    (k as f64 * sum * h / 3.0).clamp(0.0, 1.0)
+}
+
+/// The `p` quantile of the studentized range of `k` variables, with infinite
+/// degrees of freedom.
+pub fn studentized_range_quantile(p: f64, k: usize) -> f64 {
+    let (mut low, mut high) = (0.0, 20.0);
+
+    for _ in 0..60 {
+        let middle = (low + high) / 2.0;
+        if studentized_range_cdf(middle, k) < p {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erfc_works() {
+        for (x, expected) in [
+            (0.0, 1.0),
+            (0.5, 0.4795001221869535),
+            (1.0, 0.15729920705028513),
+            (2.0, 0.004677734981047266),
+            (2.5, 0.000406952017444959),
+            (3.5, 7.430983723414128e-7),
+            (6.0, 2.1519736712498913e-17),
+            (-1.0, 1.8427007929497148),
+        ] {
+            let error = (erfc(x) - expected).abs() / expected;
+            assert!(error < 1e-12, "erfc({x}) = {}", erfc(x));
+        }
+    }
+
+    #[test]
+    fn chi_squared_sf_works() {
+        for (x, df, expected) in [
+            (3.84, 1.0, 0.0500435212487051),
+            (10.0, 3.0, 0.01856613546304325),
+            (2.0, 5.0, 0.8491450360846096),
+            (50.0, 20.0, 0.0002214766382487836),
+        ] {
+            let error = (chi_squared_sf(x, df) - expected).abs() / expected;
+            assert!(error < 1e-10, "Q({x}, {df}) = {}", chi_squared_sf(x, df));
+        }
+    }
+
+    #[test]
+    fn studentized_range_works() {
+        // critical values used by the Nemenyi test, q_0.05 / √2
+        for (k, expected) in [(2, 1.959964), (3, 2.343701), (5, 2.727774), (10, 3.163684)] {
+            let q = studentized_range_quantile(0.95, k) / SQRT_2;
+            assert!((q - expected).abs() < 1e-5, "k = {k}: {q}");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::nan_last;
+
+/// Vargha-Delaney A12 effect size, the probability of a value of `x` being
+/// greater than one of `y`, with ties counting a half. For minimization,
+/// values below 0.5 mean that `x` tends to be better.
+pub fn vargha_delaney_a12(x: &[f64], y: &[f64]) -> f64 {
+    let mut wins = 0.0;
+    for &a in x {
+        for &b in y {
+            wins += match nan_last(a, b) {
+                Ordering::Greater => 1.0,
+                Ordering::Equal => 0.5,
+                Ordering::Less => 0.0,
+            };
+        }
+    }
+
+    wins / (x.len() * y.len()) as f64
+}
+
+/// Conventional interpretation of an A12 value, from its distance to 0.5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Magnitude {
+    Negligible,
+    Small,
+    Medium,
+    Large,
+}
+
+impl Magnitude {
+    pub fn of(a12: f64) -> Self {
+        match (a12 - 0.5).abs() {
+            d if d < 0.06 => Self::Negligible,
+            d if d < 0.14 => Self::Small,
+            d if d < 0.21 => Self::Medium,
+            _ => Self::Large,
+        }
+    }
+}
+
+impl fmt::Display for Magnitude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Negligible => "negligible",
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::Large => "large",
+        };
+        f.pad(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vargha_delaney_a12_works() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [2.0, 4.0];
+
+        // greater in (3, 2), tied in (2, 2)
+        assert_eq!(vargha_delaney_a12(&x, &y), 1.5 / 6.0);
+        assert_eq!(vargha_delaney_a12(&y, &x), 4.5 / 6.0);
+
+        assert_eq!(Magnitude::of(1.5 / 6.0), Magnitude::Large);
+        assert_eq!(Magnitude::of(0.45), Magnitude::Negligible);
+        assert_eq!(Magnitude::of(0.6), Magnitude::Small);
+        assert_eq!(Magnitude::of(0.68), Magnitude::Medium);
+    }
+
+    #[test]
+    fn failed_runs_are_worse() {
+        let failed = [f64::NAN; 20];
+        let runs = (0..20).map(f64::from).collect::<Vec<_>>();
+
+        assert_eq!(vargha_delaney_a12(&failed, &runs), 1.0);
+        assert_eq!(vargha_delaney_a12(&runs, &failed), 0.0);
+        assert_eq!(vargha_delaney_a12(&[f64::NAN], &[f64::NAN]), 0.5);
+
+        let comparison = crate::stats::compare(&failed, &runs, 0.05);
+        assert_eq!(comparison.outcome, crate::stats::Outcome::Worse);
+    }
+}
@@ -0,0 +1,192 @@
+use super::distributions::{
+    chi_squared_sf, normal_sf, studentized_range_cdf, studentized_range_quantile,
+};
+use super::{ranks, ties};
+
+/// Result of the Friedman test, with the post-hoc tests that compare every
+/// pair of treatments.
+#[derive(Clone, Debug)]
+pub struct Friedman {
+    /// Chi-squared statistic, corrected for ties.
+    pub statistic: f64,
+    pub p_value: f64,
+    /// Mean rank of every treatment, where rank 1 is the lowest value.
+    pub mean_ranks: Vec<f64>,
+    blocks: usize,
+}
+
+/// Friedman test of whether `k` treatments, such as algorithms, perform
+/// alike over `data`, which holds one row of `k` values per block, such as
+/// problems or seeds.
+pub fn friedman(data: &[Vec<f64>]) -> Friedman {
+    let blocks = data.len();
+    assert!(blocks > 0, "there must be at least one block");
+
+    let k = data[0].len();
+    assert!(
+        data.iter().all(|row| row.len() == k),
+        "every block must have a value per treatment"
+    );
+
+    let mut rank_sums = vec![0.0; k];
+    let mut tie_sum = 0.0;
+    for row in data {
+        for (sum, rank) in rank_sums.iter_mut().zip(ranks(row)) {
+            *sum += rank;
+        }
+        tie_sum += ties(row);
+    }
+
+    let (b, kf) = (blocks as f64, k as f64);
+
+    let statistic = 12.0 / (b * kf * (kf + 1.0)) * rank_sums.iter().map(|r| r * r).sum::<f64>()
+        - 3.0 * b * (kf + 1.0);
+    let correction = 1.0 - tie_sum / (b * kf * (kf * kf - 1.0));
+    let statistic = if correction > 0.0 {
+        statistic / correction
+    } else {
+        0.0
+    };
+
+    Friedman {
+        statistic,
+        p_value: chi_squared_sf(statistic, kf - 1.0),
+        mean_ranks: rank_sums.iter().map(|r| r / b).collect(),
+        blocks,
+    }
+}
+
+impl Friedman {
+    // standard error of the difference between two mean ranks
+    fn standard_error(&self) -> f64 {
+        let k = self.mean_ranks.len() as f64;
+        f64::sqrt(k * (k + 1.0) / (6.0 * self.blocks as f64))
+    }
+
+    /// Nemenyi p-values of every pair of treatments, `p[i][j]`.
+    pub fn nemenyi(&self) -> Vec<Vec<f64>> {
+        let k = self.mean_ranks.len();
+        let se = self.standard_error();
+
+        self.pairwise(|a, b| {
+            let q = (a - b).abs() / se * std::f64::consts::SQRT_2;
+            1.0 - studentized_range_cdf(q, k)
+        })
+    }
+
+    /// Smallest difference of mean ranks that the Nemenyi test finds
+    /// significant at level `alpha`.
+    pub fn critical_difference(&self, alpha: f64) -> f64 {
+        let k = self.mean_ranks.len();
+        studentized_range_quantile(1.0 - alpha, k) / std::f64::consts::SQRT_2
+            * self.standard_error()
+    }
+
+    /// p-values of every pair of treatments, `p[i][j]`, from the normal
+    /// approximation of the difference of their mean ranks, adjusted for the
+    /// multiple comparisons with the method of [`holm`].
+    pub fn holm(&self) -> Vec<Vec<f64>> {
+        let k = self.mean_ranks.len();
+        let se = self.standard_error();
+
+        let raw = self.pairwise(|a, b| f64::min(2.0 * normal_sf((a - b).abs() / se), 1.0));
+
+        let pairs = (0..k)
+            .flat_map(|i| (i + 1..k).map(move |j| (i, j)))
+            .collect::<Vec<_>>();
+        let adjusted = holm(&pairs.iter().map(|&(i, j)| raw[i][j]).collect::<Vec<_>>());
+
+        let mut p = vec![vec![1.0; k]; k];
+        for (&(i, j), &p_value) in pairs.iter().zip(&adjusted) {
+            p[i][j] = p_value;
+            p[j][i] = p_value;
+        }
+        p
+    }
+
+    fn pairwise<F>(&self, p_value: F) -> Vec<Vec<f64>>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        let ranks = &self.mean_ranks;
+
+        ranks
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| {
+                ranks
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &b)| if i == j { 1.0 } else { p_value(a, b) })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Holm-Bonferroni adjustment of a family of p-values, in the same order.
+pub fn holm(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+
+    let mut order = (0..m).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| p_values[a].partial_cmp(&p_values[b]).unwrap());
+
+    let mut adjusted = vec![0.0; m];
+    let mut running = 0.0;
+    for (rank, &i) in order.iter().enumerate() {
+        running = f64::max(running, ((m - rank) as f64 * p_values[i]).min(1.0));
+        adjusted[i] = running;
+    }
+
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn friedman_works() {
+        let data = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 1.0, 3.0],
+            vec![1.0, 3.0, 2.0],
+            vec![1.0, 2.0, 3.0],
+            vec![1.0, 2.0, 2.0],
+            vec![1.0, 2.0, 3.0],
+        ];
+        let result = friedman(&data);
+
+        assert_eq!(result.mean_ranks, vec![7.0 / 6.0, 12.5 / 6.0, 16.5 / 6.0]);
+        assert!((result.statistic - 7.913043478260865).abs() < 1e-12);
+        assert!((result.p_value - 0.019129536225545065).abs() < 1e-12);
+
+        let nemenyi = result.nemenyi();
+        assert!((nemenyi[0][2] - 0.01679059794910355).abs() < 1e-6);
+
+        let holm = result.holm();
+        assert!((holm[0][1] - 0.22470239538092768).abs() < 1e-10);
+        assert!((holm[0][2] - 0.018296837793643078).abs() < 1e-10);
+        assert!((holm[2][1] - 0.2482130789899237).abs() < 1e-10);
+        assert_eq!(holm[1][1], 1.0);
+    }
+
+    #[test]
+    fn failed_runs_tie() {
+        let data = vec![vec![f64::NAN, f64::NAN, 1.0]; 5];
+        let result = friedman(&data);
+
+        assert_eq!(result.mean_ranks, vec![2.5, 2.5, 1.0]);
+        assert!((result.statistic - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn holm_works() {
+        let adjusted = holm(&[0.01, 0.04, 0.03, 0.005]);
+        let expected = [0.03, 0.06, 0.06, 0.02];
+
+        for (a, e) in adjusted.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use super::{exact_p_value, normal_p_value, ranks, ties, Alternative, Method, TestResult};
+
+/// Mann-Whitney U test of independent samples, such as two algorithms run
+/// with different seeds. The statistic is the U of `x`, the number of pairs
+/// in which the value from `x` is greater, with ties counting a half.
+pub fn mann_whitney_u(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+    method: Method,
+) -> TestResult {
+    let (n1, n2) = (x.len(), y.len());
+    assert!(n1 > 0 && n2 > 0, "the samples must not be empty");
+
+    let pooled = x.iter().chain(y).copied().collect::<Vec<_>>();
+    let ranks = ranks(&pooled);
+
+    let rank_sum = ranks[..n1].iter().sum::<f64>();
+    let statistic = rank_sum - (n1 * (n1 + 1)) as f64 / 2.0;
+
+    let exact = match method {
+        Method::Exact => true,
+        Method::Normal => false,
+        Method::Auto => n1 <= 50 && n2 <= 50,
+    };
+
+    let p_value = if exact {
+        // ranks are multiples of a half, so their doubles are integers
+        let doubled = ranks.iter().map(|r| (2.0 * r) as usize).collect::<Vec<_>>();
+        let total = doubled.iter().sum::<usize>();
+
+        // counts[k][s] is the number of ways of choosing k of the ranks seen
+        // so far with a doubled sum of s
+        let mut counts = vec![vec![0.0; total + 1]; n1 + 1];
+        counts[0][0] = 1.0;
+        let mut seen = 0;
+        for (i, &r) in doubled.iter().enumerate() {
+            seen += r;
+            for k in (1..=usize::min(i + 1, n1)).rev() {
+                let (previous, current) = counts.split_at_mut(k);
+                for s in (r..=seen).rev() {
+                    current[0][s] += previous[k - 1][s - r];
+                }
+            }
+        }
+
+        // distribution of the doubled U, shifted from the doubled rank sum
+        let offset = n1 * (n1 + 1);
+        let ways = counts[n1].iter().sum::<f64>();
+        let probabilities = counts[n1][offset..]
+            .iter()
+            .map(|c| c / ways)
+            .collect::<Vec<_>>();
+
+        exact_p_value(&probabilities, (2.0 * statistic) as usize, alternative)
+    } else {
+        let (n1, n2) = (n1 as f64, n2 as f64);
+        let n = n1 + n2;
+        let mean = n1 * n2 / 2.0;
+        let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties(&pooled) / (n * (n - 1.0)));
+
+        normal_p_value(statistic - mean, variance.sqrt(), alternative)
+    };
+
+    TestResult { statistic, p_value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 8] = [19.0, 22.0, 16.0, 29.0, 24.0, 17.0, 25.0, 27.0];
+    const Y: [f64; 7] = [20.0, 11.0, 17.0, 12.0, 15.0, 10.0, 18.0];
+
+    #[test]
+    fn exact_mann_whitney_works() {
+        let result = mann_whitney_u(&X, &Y, Alternative::TwoSided, Method::Exact);
+        assert_eq!(result.statistic, 49.5);
+        assert!((result.p_value - 4.0 / 390.0).abs() < 1e-12);
+
+        let result = mann_whitney_u(&Y, &X, Alternative::Less, Method::Exact);
+        assert_eq!(result.statistic, 6.5);
+        assert!((result.p_value - 2.0 / 390.0).abs() < 1e-12);
+
+        let result = mann_whitney_u(&X, &Y, Alternative::Less, Method::Exact);
+        assert!((result.p_value - 6408.0 / 6435.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn failed_runs_tie() {
+        let failed = [f64::NAN; 10];
+        let result = mann_whitney_u(&failed, &failed, Alternative::TwoSided, Method::Exact);
+        assert_eq!(result.statistic, 50.0);
+        assert_eq!(result.p_value, 1.0);
+
+        let result = mann_whitney_u(&failed, &failed, Alternative::TwoSided, Method::Normal);
+        assert_eq!(result.p_value, 1.0);
+
+        // one run better than every failed one, and 42 tied pairs
+        let mut x = [f64::NAN; 7];
+        x[0] = 1.0;
+        let result = mann_whitney_u(&x, &[f64::NAN; 7], Alternative::TwoSided, Method::Exact);
+        assert_eq!(result.statistic, 21.0);
+        assert!(result.p_value > 0.05);
+
+        let comparison = crate::stats::compare(&failed, &failed, 0.05);
+        assert_eq!(comparison.outcome, crate::stats::Outcome::Tie);
+    }
+
+    #[test]
+    fn normal_mann_whitney_works() {
+        let result = mann_whitney_u(&X, &Y, Alternative::TwoSided, Method::Normal);
+        assert!((result.p_value - 0.014997038636807375).abs() < 1e-10);
+    }
+}
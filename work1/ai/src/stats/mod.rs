@@ -0,0 +1,129 @@
+//! Statistical comparison of the results of several algorithms. Lower values
+//! are taken to be better, as in [`Problem`](crate::metaheuristics::Problem).
+//! The rank tests take NaN, such as the value of a failed run, to be worse
+//! than any number.
+
+mod distributions;
+mod effect_size;
+mod friedman;
+mod mann_whitney;
+mod summary;
+mod table;
+mod wilcoxon;
+
+use std::cmp::Ordering;
+
+pub use effect_size::{vargha_delaney_a12, Magnitude};
+pub use friedman::{friedman, holm, Friedman};
+pub use mann_whitney::mann_whitney_u;
+pub use summary::{bootstrap, expected_running_time, quantile, success_rate, Summary};
+pub use table::{compare, Comparison, Outcome, SignificanceTable};
+pub use wilcoxon::wilcoxon_signed_rank;
+
+/// The alternative hypothesis of a test about two samples `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alternative {
+    TwoSided,
+    /// `x` tends to be smaller than `y`.
+    Less,
+    /// `x` tends to be greater than `y`.
+    Greater,
+}
+
+/// How the p-value of a rank test is computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// From the permutation distribution of the statistic, which also holds
+    /// with ties, but takes time quadratic in the size of the samples.
+    Exact,
+    /// From the normal approximation, with tie and continuity corrections.
+    Normal,
+    /// Exact for samples of up to 50 observations each, normal otherwise.
+    Auto,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+// ranks from 1, with tied values, NaN included, given the mean of the ranks
+// they span
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| nan_last(values[a], values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len()
+            && nan_last(values[order[end]], values[order[start]]) == Ordering::Equal
+        {
+            end += 1;
+        }
+
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+
+        start = end;
+    }
+
+    ranks
+}
+
+// sum of t³ - t over the groups of t tied values
+fn ties(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|&a, &b| nan_last(a, b));
+
+    sorted
+        .chunk_by(|&a, &b| nan_last(a, b) == Ordering::Equal)
+        .map(|group| {
+            let t = group.len() as f64;
+            t * t * t - t
+        })
+        .sum()
+}
+
+// p-value under the normal approximation of a statistic found at `distance`
+// from its mean, with a continuity correction of half a unit
+fn normal_p_value(distance: f64, sd: f64, alternative: Alternative) -> f64 {
+    if sd == 0.0 {
+        return 1.0;
+    }
+
+    match alternative {
+        Alternative::Greater => distributions::normal_sf((distance - 0.5) / sd),
+        Alternative::Less => distributions::normal_cdf((distance + 0.5) / sd),
+        Alternative::TwoSided => f64::min(
+            2.0 * distributions::normal_sf((distance.abs() - 0.5) / sd),
+            1.0,
+        ),
+    }
+}
+
+// p-value from the exact distribution of a statistic taking the values
+// `0..probabilities.len()`, observed at `statistic`
+fn exact_p_value(probabilities: &[f64], statistic: usize, alternative: Alternative) -> f64 {
+    let greater = probabilities[statistic..].iter().sum::<f64>();
+    let less = probabilities[..=statistic].iter().sum::<f64>();
+
+    let p_value = match alternative {
+        Alternative::Greater => greater,
+        Alternative::Less => less,
+        Alternative::TwoSided => 2.0 * f64::min(greater, less),
+    };
+
+    p_value.min(1.0)
+}
+
+// orders numbers as usual, followed by NaN
+fn nan_last(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
@@ -0,0 +1,184 @@
+use rand::distributions::{Distribution, UniformClosedOpen};
+use rand::rngs::Rng;
+
+/// Descriptive statistics of the results of several runs.
+///
+/// NaN values, such as those of runs that failed, are only counted in `nan`.
+/// Infinite values take part in the order statistics, but not in the mean or
+/// the standard deviation, and are counted in `infinite`. Statistics of no
+/// values at all are NaN.
+#[derive(Clone, Copy, Debug)]
+pub struct Summary {
+    pub count: usize,
+    pub nan: usize,
+    pub infinite: usize,
+    pub min: f64,
+    pub first_quartile: f64,
+    pub median: f64,
+    pub third_quartile: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Sample standard deviation, with Bessel's correction.
+    pub std_dev: f64,
+}
+
+impl Summary {
+    pub fn new(values: &[f64]) -> Self {
+        let mut sorted = values
+            .iter()
+            .copied()
+            .filter(|x| !x.is_nan())
+            .collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+
+        let finite = sorted
+            .iter()
+            .copied()
+            .filter(|x| x.is_finite())
+            .collect::<Vec<_>>();
+        let n = finite.len() as f64;
+
+        let mean = finite.iter().sum::<f64>() / n;
+        let variance = finite.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+
+        Self {
+            count: values.len(),
+            nan: values.len() - sorted.len(),
+            infinite: sorted.len() - finite.len(),
+            min: sorted.first().copied().unwrap_or(f64::NAN),
+            first_quartile: quantile(&sorted, 0.25),
+            median: quantile(&sorted, 0.5),
+            third_quartile: quantile(&sorted, 0.75),
+            max: sorted.last().copied().unwrap_or(f64::NAN),
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+
+    /// Interquartile range.
+    pub fn iqr(&self) -> f64 {
+        self.third_quartile - self.first_quartile
+    }
+}
+
+/// The `p` quantile of `sorted`, interpolating linearly between the closest
+/// ranks, or NaN if it is empty.
+pub fn quantile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+
+    let h = p * (sorted.len() - 1) as f64;
+    let (i, fraction) = (h.floor() as usize, h.fract());
+
+    if i + 1 < sorted.len() && fraction > 0.0 {
+        sorted[i] + fraction * (sorted[i + 1] - sorted[i])
+    } else {
+        sorted[i]
+    }
+}
+
+/// Fraction of the runs that reached `target`, where NaN never does.
+pub fn success_rate(values: &[f64], target: f64) -> f64 {
+    let successes = values.iter().filter(|&&x| x <= target).count();
+    successes as f64 / values.len() as f64
+}
+
+/// Expected running time, the number of evaluations spent on average to reach
+/// `target` for the first time, when restarting failed runs. Each run is
+/// given by its final value and the evaluations it spent, so runs that
+/// succeeded must have stopped at the target, as with
+/// [`Termination::with_target`](crate::metaheuristics::Termination::with_target).
+/// It is infinite when no run succeeded.
+pub fn expected_running_time(runs: &[(f64, usize)], target: f64) -> f64 {
+    let evaluations = runs.iter().map(|&(_, e)| e as f64).sum::<f64>();
+    let successes = runs.iter().filter(|&&(x, _)| x <= target).count();
+
+    if successes == 0 {
+        f64::INFINITY
+    } else {
+        evaluations / successes as f64
+    }
+}
+
+/// Percentile bootstrap confidence interval of `statistic` of `values`, at the
+/// given `confidence` level, from `resamples` samples drawn with replacement.
+/// Resamples whose statistic is NaN are left out.
+pub fn bootstrap<R, F>(
+    rng: &mut R,
+    values: &[f64],
+    statistic: F,
+    resamples: usize,
+    confidence: f64,
+) -> (f64, f64)
+where
+    R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
+    F: Fn(&[f64]) -> f64,
+{
+    let n = values.len();
+    let mut sample = vec![0.0; n];
+
+    let mut statistics = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        for x in &mut sample {
+            *x = values[rng.sample(&UniformClosedOpen::new(0, n))];
+        }
+
+        let s = statistic(&sample);
+        if !s.is_nan() {
+            statistics.push(s);
+        }
+    }
+    statistics.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - confidence) / 2.0;
+    (
+        quantile(&statistics, tail),
+        quantile(&statistics, 1.0 - tail),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn summary_works() {
+        let summary = Summary::new(&[4.0, f64::NAN, 1.0, 3.0, 2.0, f64::INFINITY]);
+
+        assert_eq!((summary.count, summary.nan, summary.infinite), (6, 1, 1));
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.max, f64::INFINITY);
+        assert_eq!(summary.median, 3.0);
+        assert_eq!(summary.first_quartile, 2.0);
+        assert_eq!(summary.third_quartile, 4.0);
+        assert_eq!(summary.mean, 2.5);
+        assert!((summary.std_dev - f64::sqrt(5.0 / 3.0)).abs() < 1e-12);
+
+        let summary = Summary::new(&[f64::NAN]);
+        assert!(summary.median.is_nan() && summary.mean.is_nan());
+    }
+
+    #[test]
+    fn running_time_works() {
+        let runs = [(0.0, 100), (1.0, 1000), (0.0, 300), (2.0, 1000)];
+
+        assert_eq!(success_rate(&[0.0, 1.0, f64::NAN, 0.5], 0.5), 0.5);
+        assert_eq!(expected_running_time(&runs, 0.0), 1200.0);
+        assert_eq!(expected_running_time(&runs, -1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn bootstrap_works() {
+        let mut rng = Xoshiro256Plus::new([1, 2, 3, 4]);
+        let values = (0..100).map(|i| i as f64).collect::<Vec<_>>();
+
+        let mean = |sample: &[f64]| sample.iter().sum::<f64>() / sample.len() as f64;
+        let (low, high) = bootstrap(&mut rng, &values, mean, 2000, 0.95);
+
+        // the standard error of the mean is about 2.9
+        assert!(low < 49.5 && 49.5 < high);
+        assert!((high - low - 2.0 * 1.96 * 2.9).abs() < 2.0);
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+
+use super::{
+    friedman, mann_whitney_u, nan_last, quantile, vargha_delaney_a12, Alternative, Friedman, Method,
+};
+
+/// How an algorithm did against a baseline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Better,
+    Tie,
+    Worse,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Self::Better => "+",
+            Self::Tie => "≈",
+            Self::Worse => "-",
+        };
+        f.pad(symbol)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Comparison {
+    /// Two-sided Mann-Whitney U p-value.
+    pub p_value: f64,
+    /// Vargha-Delaney A12 of the algorithm against the baseline.
+    pub a12: f64,
+    pub outcome: Outcome,
+}
+
+/// Compares the results `x` of independent runs of an algorithm with those
+/// `y` of a baseline. The difference is significant when the p-value is below
+/// `alpha`, and is then better or worse depending on the side of 0.5 the
+/// effect size falls on.
+pub fn compare(x: &[f64], y: &[f64], alpha: f64) -> Comparison {
+    let p_value = mann_whitney_u(x, y, Alternative::TwoSided, Method::Auto).p_value;
+    let a12 = vargha_delaney_a12(x, y);
+
+    let outcome = if p_value >= alpha {
+        Outcome::Tie
+    } else if a12 < 0.5 {
+        Outcome::Better
+    } else {
+        Outcome::Worse
+    };
+
+    Comparison {
+        p_value,
+        a12,
+        outcome,
+    }
+}
+
+/// Medians of every algorithm on every problem, compared with a baseline, and
+/// the Friedman test of the algorithms over the problems. Its `Display` is a
+/// plain text table.
+pub struct SignificanceTable {
+    pub algorithms: Vec<String>,
+    pub problems: Vec<String>,
+    pub baseline: usize,
+    /// `medians[p][a]` of algorithm `a` on problem `p`, with failed runs
+    /// ranked last, so that it is NaN when at least half of them failed.
+    pub medians: Vec<Vec<f64>>,
+    /// `comparisons[p][a]` against the baseline, `None` for the baseline.
+    pub comparisons: Vec<Vec<Option<Comparison>>>,
+    /// Ranks the medians, only when there are at least two problems.
+    pub friedman: Option<Friedman>,
+}
+
+impl SignificanceTable {
+    /// Builds the table from the final values of the runs, `results[p][a]`
+    /// holding those of algorithm `a` on problem `p`.
+    pub fn new(
+        algorithms: &[&str],
+        problems: &[&str],
+        results: &[Vec<Vec<f64>>],
+        baseline: usize,
+        alpha: f64,
+    ) -> Self {
+        assert_eq!(
+            results.len(),
+            problems.len(),
+            "one row of results per problem"
+        );
+        assert!(
+            results.iter().all(|row| row.len() == algorithms.len()),
+            "one sample of results per algorithm"
+        );
+
+        let medians = results
+            .iter()
+            .map(|row| row.iter().map(|runs| median(runs)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let comparisons = results
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(a, runs)| (a != baseline).then(|| compare(runs, &row[baseline], alpha)))
+                    .collect()
+            })
+            .collect();
+
+        let friedman = (problems.len() >= 2 && algorithms.len() >= 2).then(|| friedman(&medians));
+
+        Self {
+            algorithms: algorithms.iter().map(|s| s.to_string()).collect(),
+            problems: problems.iter().map(|s| s.to_string()).collect(),
+            baseline,
+            medians,
+            comparisons,
+            friedman,
+        }
+    }
+
+    /// Number of problems on which `algorithm` is better than, tied with and
+    /// worse than the baseline.
+    pub fn score(&self, algorithm: usize) -> (usize, usize, usize) {
+        let mut score = (0, 0, 0);
+        for row in &self.comparisons {
+            match row[algorithm].map(|c| c.outcome) {
+                Some(Outcome::Better) => score.0 += 1,
+                Some(Outcome::Tie) => score.1 += 1,
+                Some(Outcome::Worse) => score.2 += 1,
+                None => {}
+            }
+        }
+        score
+    }
+}
+
+impl fmt::Display for SignificanceTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rows = Vec::new();
+
+        let mut header = vec!["problem".to_string()];
+        for (a, algorithm) in self.algorithms.iter().enumerate() {
+            if a == self.baseline {
+                header.push(format!("{algorithm} (baseline)"));
+            } else {
+                header.push(algorithm.clone());
+            }
+        }
+        rows.push(header);
+
+        for ((problem, medians), comparisons) in self
+            .problems
+            .iter()
+            .zip(&self.medians)
+            .zip(&self.comparisons)
+        {
+            let mut row = vec![problem.clone()];
+            for (median, comparison) in medians.iter().zip(comparisons) {
+                row.push(match comparison {
+                    None => format!("{median:.4e}"),
+                    Some(c) => format!(
+                        "{median:.4e} p={:.1e} A12={:.2} {}",
+                        c.p_value, c.a12, c.outcome
+                    ),
+                });
+            }
+            rows.push(row);
+        }
+
+        let mut score = vec!["+/≈/-".to_string()];
+        for a in 0..self.algorithms.len() {
+            if a == self.baseline {
+                score.push(String::new());
+            } else {
+                let (better, tie, worse) = self.score(a);
+                score.push(format!("{better}/{tie}/{worse}"));
+            }
+        }
+        rows.push(score);
+
+        if let Some(friedman) = &self.friedman {
+            let mut ranks = vec!["mean rank".to_string()];
+            ranks.extend(friedman.mean_ranks.iter().map(|r| format!("{r:.2}")));
+            rows.push(ranks);
+        }
+
+        let columns = rows[0].len();
+        let widths = (0..columns)
+            .map(|c| rows.iter().map(|row| row[c].chars().count()).max().unwrap())
+            .collect::<Vec<_>>();
+
+        for row in &rows {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        if let Some(friedman) = &self.friedman {
+            writeln!(
+                f,
+                "Friedman chi2 = {:.3}, p = {:.3e}",
+                friedman.statistic, friedman.p_value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// median of the runs with NaN ranked after every number
+fn median(runs: &[f64]) -> f64 {
+    let mut sorted = runs.to_vec();
+    sorted.sort_by(|&a, &b| nan_last(a, b));
+    quantile(&sorted, 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_runs_are_ranked_last() {
+        let mut mostly_failed = vec![f64::NAN; 30];
+        mostly_failed[0] = 0.0;
+        let runs = (1..=30).map(f64::from).collect::<Vec<_>>();
+
+        let results = vec![
+            vec![runs.clone(), mostly_failed.clone()],
+            vec![runs, mostly_failed],
+        ];
+        let table = SignificanceTable::new(&["GA", "DE"], &["A", "B"], &results, 0, 0.05);
+
+        assert_eq!(table.medians[0][0], 15.5);
+        assert!(table.medians[0][1].is_nan());
+        assert_eq!(table.comparisons[0][1].unwrap().outcome, Outcome::Worse);
+        assert_eq!(table.friedman.unwrap().mean_ranks, vec![1.0, 2.0]);
+
+        assert_eq!(median(&[3.0, f64::NAN, 1.0, 2.0, f64::NAN]), 3.0);
+    }
+}
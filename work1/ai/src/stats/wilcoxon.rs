@@ -0,0 +1,138 @@
+use super::{exact_p_value, normal_p_value, ranks, ties, Alternative, Method, TestResult};
+
+/// Wilcoxon signed-rank test of paired samples, such as two algorithms run
+/// with the same seeds. Zero differences are dropped, and the statistic is
+/// the sum of the ranks of the positive differences `x - y`. A NaN is worse
+/// than any number, so it is taken as an infinite value, and pairs where both
+/// values are NaN are dropped like ties.
+pub fn wilcoxon_signed_rank(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+    method: Method,
+) -> TestResult {
+    assert_eq!(x.len(), y.len(), "the samples must be paired");
+
+    let differences = x
+        .iter()
+        .zip(y)
+        .filter_map(|(&a, &b)| match (a.is_nan(), b.is_nan()) {
+            (true, true) => None,
+            (true, false) => Some(f64::INFINITY),
+            (false, true) => Some(f64::NEG_INFINITY),
+            (false, false) => Some(a - b),
+        })
+        .filter(|&d| d != 0.0)
+        .collect::<Vec<_>>();
+    let n = differences.len();
+
+    if n == 0 {
+        return TestResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let magnitudes = differences.iter().map(|d| d.abs()).collect::<Vec<_>>();
+    let ranks = ranks(&magnitudes);
+
+    let statistic = differences
+        .iter()
+        .zip(&ranks)
+        .filter(|(&d, _)| d > 0.0)
+        .map(|(_, &r)| r)
+        .sum::<f64>();
+
+    let exact = match method {
+        Method::Exact => true,
+        Method::Normal => false,
+        Method::Auto => n <= 50,
+    };
+
+    let p_value = if exact {
+        // ranks are multiples of a half, so their doubles are integers
+        let doubled = ranks.iter().map(|r| (2.0 * r) as usize).collect::<Vec<_>>();
+
+        // each rank is positive with probability 1/2
+        let mut probabilities = vec![0.0; doubled.iter().sum::<usize>() + 1];
+        probabilities[0] = 1.0;
+        let mut total = 0;
+        for &r in &doubled {
+            total += r;
+            for s in (0..=total).rev() {
+                let without = probabilities[s];
+                let with = if s >= r { probabilities[s - r] } else { 0.0 };
+                probabilities[s] = (without + with) / 2.0;
+            }
+        }
+
+        exact_p_value(&probabilities, (2.0 * statistic) as usize, alternative)
+    } else {
+        let n = n as f64;
+        let mean = n * (n + 1.0) / 4.0;
+        let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties(&magnitudes) / 48.0;
+
+        normal_p_value(statistic - mean, variance.sqrt(), alternative)
+    };
+
+    TestResult { statistic, p_value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 10] = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30, 2.01];
+    const Y: [f64; 10] = [
+        0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.24, 1.29, 1.10,
+    ];
+
+    #[test]
+    fn exact_wilcoxon_works() {
+        let result = wilcoxon_signed_rank(&X, &Y, Alternative::TwoSided, Method::Exact);
+        assert_eq!(result.statistic, 50.0);
+        assert!((result.p_value - 0.01953125).abs() < 1e-12);
+
+        let result = wilcoxon_signed_rank(&X, &Y, Alternative::Greater, Method::Exact);
+        assert!((result.p_value - 0.009765625).abs() < 1e-12);
+
+        let result = wilcoxon_signed_rank(&X, &Y, Alternative::Less, Method::Exact);
+        assert!((result.p_value - 0.9931640625).abs() < 1e-12);
+    }
+
+    #[test]
+    fn exact_wilcoxon_with_ties_works() {
+        // |differences| 1, 1, 2, 3, 3, 3 with ranks 1.5, 1.5, 3, 5, 5, 5
+        let x = [1.0, 2.0, 5.0, 3.0, 7.0, 0.0, 4.0];
+        let y = [0.0, 3.0, 3.0, 0.0, 4.0, 3.0, 4.0];
+
+        let result = wilcoxon_signed_rank(&x, &y, Alternative::Greater, Method::Exact);
+        assert_eq!(result.statistic, 14.5);
+        assert!((result.p_value - 17.0 / 64.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn normal_wilcoxon_works() {
+        let result = wilcoxon_signed_rank(&X, &Y, Alternative::TwoSided, Method::Normal);
+        assert!((result.p_value - 0.024932455602863116).abs() < 1e-10);
+    }
+
+    #[test]
+    fn failed_runs_are_worse() {
+        let failed = [f64::NAN; 20];
+        let runs = (0..20).map(f64::from).collect::<Vec<_>>();
+
+        let result = wilcoxon_signed_rank(&failed, &runs, Alternative::Greater, Method::Exact);
+        assert_eq!(result.statistic, 210.0);
+        assert!(result.p_value < 1e-5);
+
+        let result = wilcoxon_signed_rank(&failed, &runs, Alternative::Less, Method::Exact);
+        assert_eq!(result.p_value, 1.0);
+
+        // only the pair where `y` failed is left
+        let x = [f64::NAN, 1.0];
+        let y = [f64::NAN, f64::NAN];
+        let result = wilcoxon_signed_rank(&x, &y, Alternative::Greater, Method::Exact);
+        assert_eq!(result.statistic, 0.0);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::rngs::Rng;
+
+use super::{Distribution, StandardNormal};
+
+/// Symmetric Lévy-stable steps of index `beta`, in (0, 2], drawn with
+/// Mantegna's algorithm as `u / |v|^(1 / beta)`, where `v` is standard normal
+/// and `u` is normal with Mantegna's standard deviation for `beta`.
+pub struct Levy<T> {
+    beta: T,
+    sigma: T,
+}
+
+impl Levy<f32> {
+    pub fn new(beta: f32) -> Self {
+        Self {
+            beta,
+            sigma: sigma(beta as f64) as f32,
+        }
+    }
+}
+
+impl Levy<f64> {
+    pub fn new(beta: f64) -> Self {
+        Self {
+            beta,
+            sigma: sigma(beta),
+        }
+    }
+}
+
+macro levy_distribution_impl($fty:ty) {
+    impl Distribution<$fty> for Levy<$fty> {
+        type Backend = <StandardNormal as Distribution<$fty>>::Backend;
+
+        fn sample<R>(&self, rng: &mut R) -> $fty
+        where
+            R: Rng<Self::Backend> + ?Sized,
+        {
+            let u = rng.sample::<$fty, _>(&StandardNormal) * self.sigma;
+            let v = rng.sample::<$fty, _>(&StandardNormal);
+
+            u / <$fty>::powf(v.abs(), 1.0 / self.beta)
+        }
+    }
+}
+
+levy_distribution_impl! { f32 }
+levy_distribution_impl! { f64 }
+
+// standard deviation of `u` in Mantegna's algorithm, which also checks
+// `beta` for both constructors
+fn sigma(beta: f64) -> f64 {
+    assert!(
+        beta > 0.0 && beta <= 2.0,
+        "beta must be in (0, 2], not {beta}"
+    );
+
+    let numerator = gamma(1.0 + beta) * f64::sin(std::f64::consts::PI * beta / 2.0);
+    let denominator = gamma((1.0 + beta) / 2.0) * beta * f64::powf(2.0, (beta - 1.0) / 2.0);
+
+    f64::powf(numerator / denominator, 1.0 / beta)
+}
+
+// Lanczos approximation with g = 7, and the reflection formula below 1/2
+fn gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        return std::f64::consts::PI / (f64::sin(std::f64::consts::PI * x) * gamma(1.0 - x));
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+
+    f64::sqrt(std::f64::consts::TAU) * t.powf(x + 0.5) * f64::exp(-t) * sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn levy_works() {
+        // the usual value for the steps of cuckoo search
+        let distribution = Levy::<f64>::new(1.5);
+        assert!((distribution.sigma - 0.696_574_502_557_696_8).abs() < 1e-12);
+
+        let mut rng = Xoshiro256Plus::new([
+            0x93920339ac7730ac,
+            0x8db68f4acc7c22b1,
+            0x8b804df6a99a1289,
+            0xff5fa2f037375aa9,
+        ]);
+
+        // with beta = 1 the steps are Cauchy, whose absolute value has a
+        // median of 1
+        let n = 100000;
+        let distribution = Levy::<f64>::new(1.0);
+
+        let mut samples = (0..n)
+            .map(|_| rng.sample::<f64, _>(&distribution).abs())
+            .collect::<Vec<_>>();
+        samples.sort_by(f64::total_cmp);
+
+        assert!((samples[n / 2] - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    #[should_panic(expected = "beta must be in (0, 2]")]
+    fn beta_above_two_is_rejected() {
+        Levy::<f64>::new(2.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "beta must be in (0, 2]")]
+    fn beta_of_zero_is_rejected() {
+        Levy::<f32>::new(0.0);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::rngs::Rng;
 
+mod levy;
+mod normal;
 mod uniform;
 
+pub use levy::Levy;
+pub use normal::{Normal, StandardNormal};
 pub use uniform::{
     UniformClosedOpen, UniformClosedOpen01, UniformOpenClosed, UniformOpenClosed01,
     UniformOpenOpen, UniformOpenOpen01,
@@ -0,0 +1,85 @@
+use crate::rngs::Rng;
+
+use super::{Distribution, UniformClosedOpen01, UniformOpenClosed01};
+
+pub struct StandardNormal;
+
+macro standard_normal_distribution_impl($fty:ty, $tau:expr) {
+    // Box-Muller transform, only the cosine variate is used
+    impl Distribution<$fty> for StandardNormal {
+        type Backend = <UniformOpenClosed01 as Distribution<$fty>>::Backend;
+
+        fn sample<R>(&self, rng: &mut R) -> $fty
+        where
+            R: Rng<Self::Backend> + ?Sized,
+        {
+            // u1 must be in (0, 1] so that the logarithm is finite
+            let u1 = rng.sample::<$fty, _>(&UniformOpenClosed01);
+            let u2 = rng.sample::<$fty, _>(&UniformClosedOpen01);
+
+            <$fty>::sqrt(-2.0 * <$fty>::ln(u1)) * <$fty>::cos($tau * u2)
+        }
+    }
+}
+
+standard_normal_distribution_impl! { f32, std::f32::consts::TAU }
+standard_normal_distribution_impl! { f64, std::f64::consts::TAU }
+
+pub struct Normal<T> {
+    mean: T,
+    std_dev: T,
+}
+impl<T> Normal<T> {
+    pub fn new(mean: T, std_dev: T) -> Self {
+        Self { mean, std_dev }
+    }
+}
+
+macro normal_distribution_impl($fty:ty) {
+    impl Distribution<$fty> for Normal<$fty> {
+        type Backend = <StandardNormal as Distribution<$fty>>::Backend;
+
+        fn sample<R>(&self, rng: &mut R) -> $fty
+        where
+            R: Rng<Self::Backend> + ?Sized,
+        {
+            rng.sample::<$fty, _>(&StandardNormal) * self.std_dev + self.mean
+        }
+    }
+}
+
+normal_distribution_impl! { f32 }
+normal_distribution_impl! { f64 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rngs::Xoshiro256Plus;
+
+    #[test]
+    fn normal_moments_work() {
+        let mut rng = Xoshiro256Plus::new([
+            0x93920339ac7730ac,
+            0x8db68f4acc7c22b1,
+            0x8b804df6a99a1289,
+            0xff5fa2f037375aa9,
+        ]);
+
+        let n = 100000;
+        let distribution = Normal::new(3.0, 2.0);
+
+        let samples = (0..n)
+            .map(|_| rng.sample::<f64, _>(&distribution))
+            .collect::<Vec<_>>();
+
+        let mean = samples.iter().sum::<f64>() / (n as f64);
+        let variance = samples
+            .iter()
+            .map(|&x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / ((n - 1) as f64);
+
+        assert!((mean - 3.0).abs() < 0.05);
+        assert!((variance - 4.0).abs() < 0.1);
+    }
+}
@@ -125,3 +125,68 @@ macro uniform_distributions_impl($fty:ty) {
 
 uniform_distributions_impl! { f32 }
 uniform_distributions_impl! { f64 }
+
+// Lemire's nearly divisionless method, the product of a random word and the
+// length of the range is rejected in the few cases that would bias it
+macro integer_uniform_distribution_impl($ity:ty, $uty:ty, $wty:ty) {
+    impl Distribution<$ity> for UniformClosedOpen<$ity> {
+        type Backend = $uty;
+
+        fn sample<R>(&self, rng: &mut R) -> $ity
+        where
+            R: Rng<Self::Backend> + ?Sized,
+        {
+            assert!(self.start < self.end, "the range must not be empty");
+
+            let length = (self.end - self.start) as $uty;
+            let mut product = (rng.gen() as $wty) * (length as $wty);
+
+            if (product as $uty) < length {
+                let threshold = length.wrapping_neg() % length;
+                while (product as $uty) < threshold {
+                    product = (rng.gen() as $wty) * (length as $wty);
+                }
+            }
+
+            self.start + (product >> <$uty>::BITS) as $ity
+        }
+    }
+}
+
+integer_uniform_distribution_impl! { u32, u32, u64 }
+integer_uniform_distribution_impl! { u64, u64, u128 }
+integer_uniform_distribution_impl! { usize, u64, u128 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rngs::{Xoshiro128PlusPlus, Xoshiro256PlusPlus};
+
+    #[test]
+    fn uniform_integers_work() {
+        let mut rng = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+
+        let n = 60000;
+        let distribution = UniformClosedOpen::<usize>::new(4, 7);
+
+        let mut counts = [0; 3];
+        for _ in 0..n {
+            counts[rng.sample::<usize, _>(&distribution) - 4] += 1;
+        }
+        for count in counts {
+            assert!((count as f64 - n as f64 / 3.0).abs() < 500.0, "{counts:?}");
+        }
+
+        // about half of the words are rejected for this length
+        let mut rng = Xoshiro128PlusPlus::new([1, 2, 3, 4]);
+        let distribution = UniformClosedOpen::<u32>::new(0, (1 << 31) + 1);
+        let below = (0..n)
+            .filter(|_| rng.sample::<u32, _>(&distribution) < 1 << 30)
+            .count();
+        assert!((below as f64 - n as f64 / 2.0).abs() < 600.0, "{below}");
+
+        let distribution = UniformClosedOpen::<u64>::new(u64::MAX - 1, u64::MAX);
+        let mut rng = Xoshiro256PlusPlus::new([5, 6, 7, 8]);
+        assert_eq!(rng.sample::<u64, _>(&distribution), u64::MAX - 1);
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 use crate::distributions::Distribution;
 
 mod xoshiro128plus;
@@ -20,3 +23,166 @@ pub trait Rng<T> {
         distribution.sample(self)
     }
 }
+
+/// Why the text encoding of a generator could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text is not tagged with the name of the expected generator.
+    WrongAlgorithm {
+        expected: &'static str,
+        found: String,
+    },
+    /// The state after the tag does not have the expected number of characters.
+    WrongLength { expected: usize, found: usize },
+    /// The state is not made of four dash separated hexadecimal words.
+    InvalidDigit,
+    /// The state is zero everywhere, from which the generators never leave.
+    ZeroState,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongAlgorithm { expected, found } => {
+                write!(f, "expected a {expected} state, found {found:?}")
+            }
+            Self::WrongLength { expected, found } => {
+                write!(
+                    f,
+                    "expected a state of {expected} characters, found {found}"
+                )
+            }
+            Self::InvalidDigit => write!(f, "the state is not four hexadecimal words"),
+            Self::ZeroState => write!(f, "the state is zero everywhere"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// The text encoding is the name of the generator followed by its state as
+// fixed width lowercase hexadecimal words, like
+// `xoshiro256+:93920339ac7730ac-5c6e3b2a4c58f1c5-2b6e1a1d0e13f4d2-8e2f40b8fc1a6d6b`,
+// and is kept stable so that it can be stored.
+macro text_encoding_impl($rng:ty, $uty:ty, $name:expr) {
+    impl fmt::Display for $rng {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            const DIGITS: usize = <$uty>::BITS as usize / 4;
+
+            let [s0, s1, s2, s3] = self.state();
+            write!(
+                f,
+                "{}:{s0:0DIGITS$x}-{s1:0DIGITS$x}-{s2:0DIGITS$x}-{s3:0DIGITS$x}",
+                $name
+            )
+        }
+    }
+
+    impl FromStr for $rng {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            const DIGITS: usize = <$uty>::BITS as usize / 4;
+
+            let wrong_algorithm = || ParseError::WrongAlgorithm {
+                expected: $name,
+                found: s.split(':').next().unwrap_or_default().to_string(),
+            };
+
+            let (name, words) = s.split_once(':').ok_or_else(wrong_algorithm)?;
+            if name != $name {
+                return Err(wrong_algorithm());
+            }
+
+            if words.len() != 4 * DIGITS + 3 {
+                return Err(ParseError::WrongLength {
+                    expected: 4 * DIGITS + 3,
+                    found: words.len(),
+                });
+            }
+
+            let mut state = [0; 4];
+            let mut words = words.split('-');
+            for word in &mut state {
+                let digits = words.next().ok_or(ParseError::InvalidDigit)?;
+                if digits.len() != DIGITS || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(ParseError::InvalidDigit);
+                }
+                *word = <$uty>::from_str_radix(digits, 16).unwrap();
+            }
+
+            if state == [0; 4] {
+                return Err(ParseError::ZeroState);
+            }
+
+            Ok(Self::from_state(state))
+        }
+    }
+}
+
+text_encoding_impl! { Xoshiro128Plus, u32, "xoshiro128+" }
+text_encoding_impl! { Xoshiro128PlusPlus, u32, "xoshiro128++" }
+text_encoding_impl! { Xoshiro256Plus, u64, "xoshiro256+" }
+text_encoding_impl! { Xoshiro256PlusPlus, u64, "xoshiro256++" }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_encoding_round_trips() {
+        let mut rng = Xoshiro256PlusPlus::new([1, 2, 3, 4]);
+        rng.next_u64();
+
+        let text = rng.to_string();
+        let mut parsed = text.parse::<Xoshiro256PlusPlus>().unwrap();
+
+        assert_eq!(parsed, rng);
+        assert_eq!(parsed.next_u64(), rng.next_u64());
+
+        let rng = Xoshiro128Plus::new([0xdeadbeef, 1, 0, 0xffffffff]);
+        assert_eq!(
+            rng.to_string(),
+            "xoshiro128+:deadbeef-00000001-00000000-ffffffff"
+        );
+        assert_eq!(rng.to_string().parse(), Ok(rng));
+    }
+
+    #[test]
+    fn text_encoding_errors_work() {
+        assert_eq!(
+            "xoshiro256+:0000000000000001-0000000000000002-0000000000000003-0000000000000004"
+                .parse::<Xoshiro256PlusPlus>(),
+            Err(ParseError::WrongAlgorithm {
+                expected: "xoshiro256++",
+                found: "xoshiro256+".to_string(),
+            })
+        );
+        assert_eq!(
+            "0000000000000001".parse::<Xoshiro256Plus>(),
+            Err(ParseError::WrongAlgorithm {
+                expected: "xoshiro256+",
+                found: "0000000000000001".to_string(),
+            })
+        );
+        assert_eq!(
+            "xoshiro128++:00000001-00000002-00000003".parse::<Xoshiro128PlusPlus>(),
+            Err(ParseError::WrongLength {
+                expected: 35,
+                found: 26
+            })
+        );
+        assert_eq!(
+            "xoshiro128++:00000001-00000002-0000000g-00000004".parse::<Xoshiro128PlusPlus>(),
+            Err(ParseError::InvalidDigit)
+        );
+        assert_eq!(
+            "xoshiro128++:00000001-000000020-0000003-00000004".parse::<Xoshiro128PlusPlus>(),
+            Err(ParseError::InvalidDigit)
+        );
+        assert_eq!(
+            "xoshiro128++:00000000-00000000-00000000-00000000".parse::<Xoshiro128PlusPlus>(),
+            Err(ParseError::ZeroState)
+        );
+    }
+}
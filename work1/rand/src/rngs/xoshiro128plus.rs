@@ -12,6 +12,7 @@ use super::Rng;
 /// right shifts to extract subsets of bits.
 ///
 /// The state must be seeded so that it is not everywhere zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Xoshiro128Plus {
     s: [u32; 4],
 }
@@ -21,6 +22,16 @@ impl Xoshiro128Plus {
         Self { s }
     }
 
+    /// Returns the current state, from which the generator can be recreated
+    /// with `from_state` to continue the same stream.
+    pub fn state(&self) -> [u32; 4] {
+        self.s
+    }
+
+    pub fn from_state(s: [u32; 4]) -> Self {
+        Self { s }
+    }
+
     pub fn next_u32(&mut self) -> u32 {
         let result = u32::wrapping_add(self.s[0], self.s[3]);
 
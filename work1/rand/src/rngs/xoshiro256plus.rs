@@ -14,6 +14,7 @@ use super::Rng;
 /// The state must be seeded so that it is not everywhere zero. If you have
 /// a 64-bit seed, we suggest to seed a splitmix64 generator and use its
 /// output to fill s.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Xoshiro256Plus {
     s: [u64; 4],
 }
@@ -23,6 +24,16 @@ impl Xoshiro256Plus {
         Self { s }
     }
 
+    /// Returns the current state, from which the generator can be recreated
+    /// with `from_state` to continue the same stream.
+    pub fn state(&self) -> [u64; 4] {
+        self.s
+    }
+
+    pub fn from_state(s: [u64; 4]) -> Self {
+        Self { s }
+    }
+
     pub fn next_u64(&mut self) -> u64 {
         let result = u64::wrapping_add(self.s[0], self.s[3]);
 
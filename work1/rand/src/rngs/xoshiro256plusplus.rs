@@ -10,6 +10,7 @@ use super::Rng;
 /// The state must be seeded so that it is not everywhere zero. If you have
 /// a 64-bit seed, we suggest to seed a splitmix64 generator and use its
 /// output to fill s.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Xoshiro256PlusPlus {
     s: [u64; 4],
 }
@@ -19,6 +20,16 @@ impl Xoshiro256PlusPlus {
         Self { s }
     }
 
+    /// Returns the current state, from which the generator can be recreated
+    /// with `from_state` to continue the same stream.
+    pub fn state(&self) -> [u64; 4] {
+        self.s
+    }
+
+    pub fn from_state(s: [u64; 4]) -> Self {
+        Self { s }
+    }
+
     pub fn next_u64(&mut self) -> u64 {
         let result = u64::wrapping_add(
             u64::wrapping_add(self.s[0], self.s[3]).rotate_left(23),
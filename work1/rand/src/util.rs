@@ -4,12 +4,12 @@ use crate::rngs::Rng;
 // Fisher-Yates shuffle
 pub fn shuffle<R, T>(rng: &mut R, array: &mut [T])
 where
-    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+    R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
 {
     let n = array.len();
 
-    for i in 0..n - 1 {
-        let j = rng.sample(&UniformClosedOpen::new(i as f64, n as f64)) as usize;
+    for i in 0..n.saturating_sub(1) {
+        let j = rng.sample(&UniformClosedOpen::new(i, n));
         array.swap(i, j);
     }
 }
@@ -17,17 +17,17 @@ where
 // partial Fisher-Yates shuffle
 pub fn partial_shuffle<'a, R, T>(rng: &mut R, array: &'a mut [T], amount: usize) -> &'a [T]
 where
-    R: Rng<<UniformClosedOpen<f64> as Distribution<f64>>::Backend>,
+    R: Rng<<UniformClosedOpen<usize> as Distribution<usize>>::Backend>,
 {
-    let mut n = array.len();
-    if amount < n {
-        n = amount;
-    }
+    let n = array.len();
+    let amount = usize::min(amount, n);
 
-    for i in 0..n - 1 {
-        let j = rng.sample(&UniformClosedOpen::new(i as f64, n as f64)) as usize;
+    // the swapped element must be drawn from the whole array, not only from
+    // the prefix being returned
+    for i in 0..amount {
+        let j = rng.sample(&UniformClosedOpen::new(i, n));
         array.swap(i, j);
     }
 
-    &array[..n]
+    &array[..amount]
 }